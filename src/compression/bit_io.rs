@@ -0,0 +1,135 @@
+// 按位读写字节序列，高位在前
+// 写入的位数不是 8 的倍数时，最后一个字节的低位补 0
+
+#[derive(Debug, Default)]
+pub struct BitWriter {
+    buf: Vec<u8>,
+    // 最后一个字节已经写入的位数，0 表示需要新的字节
+    used: usize,
+}
+
+impl BitWriter {
+    pub fn new() -> Self {
+        BitWriter { buf: Vec::new(), used: 0 }
+    }
+
+    pub fn write_bit(&mut self, bit: bool) {
+        if self.used == 0 {
+            self.buf.push(0);
+        }
+
+        if bit {
+            *self.buf.last_mut().unwrap() |= 1 << (7 - self.used);
+        }
+
+        self.used = (self.used + 1) % 8;
+    }
+
+    // 写入 value 的低 width 位
+    pub fn write_bits(&mut self, value: u64, width: usize) {
+        for i in (0..width).rev() {
+            self.write_bit((value >> i) & 1 == 1);
+        }
+    }
+
+    pub fn write_byte(&mut self, byte: u8) {
+        self.write_bits(byte as u64, 8);
+    }
+
+    // 已经写入的位数
+    pub fn len(&self) -> usize {
+        match self.used {
+            0 => self.buf.len() * 8,
+            used => (self.buf.len() - 1) * 8 + used,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+#[derive(Debug)]
+pub struct BitReader<'a> {
+    buf: &'a [u8],
+    // 下一个要读的位
+    pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        BitReader { buf, pos: 0 }
+    }
+
+    // 没有剩余的位时返回 None
+    pub fn read_bit(&mut self) -> Option<bool> {
+        let byte = *self.buf.get(self.pos / 8)?;
+        let bit = (byte >> (7 - self.pos % 8)) & 1 == 1;
+        self.pos += 1;
+        Some(bit)
+    }
+
+    // 读取 width 位，剩余的位不足时返回 None
+    pub fn read_bits(&mut self, width: usize) -> Option<u64> {
+        if self.remaining() < width {
+            return None
+        }
+
+        let mut value = 0;
+
+        for _ in 0..width {
+            value = (value << 1) | self.read_bit()? as u64;
+        }
+
+        Some(value)
+    }
+
+    pub fn read_byte(&mut self) -> Option<u8> {
+        self.read_bits(8).map(|byte| byte as u8)
+    }
+
+    // 剩余的位数
+    pub fn remaining(&self) -> usize {
+        self.buf.len() * 8 - self.pos
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+}
+
+#[test]
+fn test() {
+    let mut writer = BitWriter::new();
+    assert!(writer.is_empty());
+
+    writer.write_bit(true);
+    writer.write_bits(0b010, 3);
+    writer.write_byte(0xFF);
+    assert_eq!(writer.len(), 12);
+
+    writer.write_bits(0xABC, 12);
+    assert_eq!(writer.len(), 24);
+
+    let bytes = writer.finish();
+    assert_eq!(bytes, [0b1010_1111, 0b1111_1010, 0b1011_1100]);
+
+    let mut reader = BitReader::new(&bytes);
+    assert_eq!(reader.read_bit(), Some(true));
+    assert_eq!(reader.read_bits(3), Some(0b010));
+    assert_eq!(reader.read_byte(), Some(0xFF));
+    assert_eq!(reader.remaining(), 12);
+    assert_eq!(reader.read_bits(13), None);
+    assert_eq!(reader.read_bits(12), Some(0xABC));
+    assert!(reader.is_empty());
+    assert_eq!(reader.read_bit(), None);
+
+    // 补齐的 0
+    let mut writer = BitWriter::new();
+    writer.write_bits(0b101, 3);
+    assert_eq!(writer.finish(), [0b1010_0000]);
+}
@@ -0,0 +1,222 @@
+// 霍夫曼压缩
+// 用索引优先队列反复合并频率最小的两棵树，构造前缀码的单词查找树
+// 输出依次为：原始长度（64 位）、前序遍历的单词查找树、编码后的比特流
+use std::cmp::Reverse;
+use super::bit_io::{BitReader, BitWriter};
+use super::super::queue::index_binary_heap::IndexBinaryHeap;
+
+const R: usize = 256;
+
+// 单词查找树的结点保存在数组中，子结点用下标表示
+struct Node {
+    ch: u8,
+    freq: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+impl Node {
+    fn is_leaf(&self) -> bool {
+        self.left.is_none() && self.right.is_none()
+    }
+}
+
+// 返回所有结点和根结点的下标
+fn build_trie(freq: &[usize]) -> (Vec<Node>, usize) {
+    let mut nodes = Vec::with_capacity(2 * R);
+
+    for (c, &f) in freq.iter().enumerate() {
+        if f > 0 {
+            nodes.push(Node { ch: c as u8, freq: f, left: None, right: None });
+        }
+    }
+
+    // 只有一种字节时加入一个频率为 0 的字节，保证每个字节的编码至少有一位
+    if nodes.len() == 1 {
+        let ch = if nodes[0].ch == 0 { 1 } else { 0 };
+        nodes.push(Node { ch, freq: 0, left: None, right: None });
+    }
+
+    // 最大堆中保存 Reverse，频率相同时按下标，结果是确定的
+    let mut pq = IndexBinaryHeap::with_capacity(2 * R);
+
+    for (i, node) in nodes.iter().enumerate() {
+        pq.put(i, Reverse((node.freq, i)));
+    }
+
+    while pq.size() > 1 {
+        let left = pq.pop();
+        let right = pq.pop();
+        let i = nodes.len();
+        let freq = nodes[left].freq + nodes[right].freq;
+
+        nodes.push(Node { ch: 0, freq, left: Some(left), right: Some(right) });
+        pq.put(i, Reverse((freq, i)));
+    }
+
+    let root = pq.pop();
+    (nodes, root)
+}
+
+// 由单词查找树构造编译表，code[c] 为字节 c 的编码
+fn build_code(nodes: &[Node], x: usize, path: &mut Vec<bool>, code: &mut Vec<Vec<bool>>) {
+    let node = &nodes[x];
+
+    if node.is_leaf() {
+        code[node.ch as usize] = path.clone();
+        return
+    }
+
+    path.push(false);
+    build_code(nodes, node.left.unwrap(), path, code);
+    path.pop();
+
+    path.push(true);
+    build_code(nodes, node.right.unwrap(), path, code);
+    path.pop();
+}
+
+// 前序遍历写入单词查找树，叶子结点写 1 和字节，内部结点写 0
+fn write_trie(nodes: &[Node], x: usize, writer: &mut BitWriter) {
+    let node = &nodes[x];
+
+    if node.is_leaf() {
+        writer.write_bit(true);
+        writer.write_byte(node.ch);
+        return
+    }
+
+    writer.write_bit(false);
+    write_trie(nodes, node.left.unwrap(), writer);
+    write_trie(nodes, node.right.unwrap(), writer);
+}
+
+fn read_trie(reader: &mut BitReader, nodes: &mut Vec<Node>) -> usize {
+    let node = if reader.read_bit().expect("truncated huffman trie") {
+        let ch = reader.read_byte().expect("truncated huffman trie");
+        Node { ch, freq: 0, left: None, right: None }
+    }
+    else {
+        let left = read_trie(reader, nodes);
+        let right = read_trie(reader, nodes);
+        Node { ch: 0, freq: 0, left: Some(left), right: Some(right) }
+    };
+
+    nodes.push(node);
+    nodes.len() - 1
+}
+
+pub fn compress(data: &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter::new();
+    writer.write_bits(data.len() as u64, 64);
+
+    if data.is_empty() {
+        return writer.finish()
+    }
+
+    let mut freq = vec![0; R];
+
+    for &c in data {
+        freq[c as usize] += 1;
+    }
+
+    let (nodes, root) = build_trie(&freq);
+    let mut code = vec![Vec::new(); R];
+    build_code(&nodes, root, &mut Vec::new(), &mut code);
+    write_trie(&nodes, root, &mut writer);
+
+    for &c in data {
+        for &bit in code[c as usize].iter() {
+            writer.write_bit(bit);
+        }
+    }
+
+    writer.finish()
+}
+
+// 输入不是 compress 的输出时会 panic
+pub fn expand(data: &[u8]) -> Vec<u8> {
+    let mut reader = BitReader::new(data);
+    let len = reader.read_bits(64).expect("truncated huffman header") as usize;
+    // 每个编码至少一位，长度不可信时不会预先分配过多的内存
+    let mut result = Vec::with_capacity(len.min(reader.remaining()));
+
+    if len == 0 {
+        return result
+    }
+
+    let mut nodes = Vec::new();
+    let root = read_trie(&mut reader, &mut nodes);
+
+    // compress 总会生成至少两个叶子，只有一个叶子时解码不消耗任何位
+    assert!(! nodes[root].is_leaf(), "corrupt huffman trie");
+
+    for _ in 0..len {
+        let mut x = root;
+
+        while ! nodes[x].is_leaf() {
+            x = if reader.read_bit().expect("truncated huffman stream") {
+                nodes[x].right.unwrap()
+            }
+            else {
+                nodes[x].left.unwrap()
+            };
+        }
+
+        result.push(nodes[x].ch);
+    }
+
+    result
+}
+
+#[test]
+fn test() {
+    extern crate rand;
+
+    let data = b"ABRACADABRA!";
+    let compressed = compress(data);
+
+    // 8 字节长度 + 单词查找树 (6 个叶子 * 9 + 5 个内部结点) 位 + 编码 28 位
+    assert_eq!(compressed.len(), 8 + (59 + 28_usize).div_ceil(8));
+    assert_eq!(expand(&compressed), data.to_vec());
+
+    // 频率越高编码越短
+    let mut freq = vec![0; R];
+
+    for &c in data.iter() {
+        freq[c as usize] += 1;
+    }
+
+    let (nodes, root) = build_trie(&freq);
+    let mut code = vec![Vec::new(); R];
+    build_code(&nodes, root, &mut Vec::new(), &mut code);
+    assert_eq!(code[b'A' as usize].len(), 1);
+    assert!(code[b'!' as usize].len() > code[b'B' as usize].len());
+
+    // 空输入和只有一种字节
+    assert_eq!(expand(&compress(b"")), b"");
+    assert_eq!(expand(&compress(b"aaaa")), b"aaaa");
+    assert_eq!(expand(&compress(&[0, 0, 0])), [0, 0, 0]);
+
+    let data: Vec<u8> = (0..2000).map(|_| rand::random::<u8>() % 16).collect();
+    let compressed = compress(&data);
+    assert!(compressed.len() < data.len());
+    assert_eq!(expand(&compressed), data);
+
+    let text = "压缩中文文本，中文文本的字节分布并不均匀".repeat(20);
+    assert_eq!(expand(&compress(text.as_bytes())), text.as_bytes());
+}
+
+#[test]
+#[should_panic(expected = "truncated huffman")]
+fn test_corrupt_header() {
+    // 长度字段为 u64::MAX，后面没有数据
+    expand(&[0xff; 9]);
+}
+
+#[test]
+#[should_panic(expected = "corrupt huffman trie")]
+fn test_single_leaf() {
+    // 长度字段为 u64::MAX，单词查找树只有一个叶子
+    expand(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x80]);
+}
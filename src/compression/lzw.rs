@@ -0,0 +1,128 @@
+// LZW 压缩
+// 编译表是一个 TrieST，每次找出输入中已经在表中的最长前缀，输出它的编码
+// 再把这个前缀加上下一个字节作为新的键加入表中
+// 编码都是 12 位，256 表示输入结束
+use super::bit_io::{BitReader, BitWriter};
+use super::super::string::trie_st::TrieST;
+
+const R: usize = 256;
+const L: usize = 4096;
+const W: usize = 12;
+
+// TrieST 的键是字符串，每个字节映射为 U+0000 到 U+00FF 之间的一个字符
+fn to_key(data: &[u8]) -> String {
+    data.iter().map(|&c| c as char).collect()
+}
+
+pub fn compress(data: &[u8]) -> Vec<u8> {
+    let mut st = TrieST::new();
+
+    for c in 0..R {
+        st.put(&to_key(&[c as u8]), c);
+    }
+
+    // 下一个可用的编码，R 留给结束符
+    let mut code = R + 1;
+    let input = to_key(data);
+    let mut rest = input.as_str();
+    let mut writer = BitWriter::new();
+
+    while ! rest.is_empty() {
+        // 单个字节一定在表中
+        let s = st.longest_prefix_of(rest).unwrap();
+        writer.write_bits(st.get(s).unwrap() as u64, W);
+
+        if let Some(c) = rest[s.len()..].chars().next() {
+            if code < L {
+                st.put(&rest[..s.len() + c.len_utf8()], code);
+                code += 1;
+            }
+        }
+
+        rest = &rest[s.len()..];
+    }
+
+    writer.write_bits(R as u64, W);
+    writer.finish()
+}
+
+// 输入不是 compress 的输出时会 panic
+pub fn expand(data: &[u8]) -> Vec<u8> {
+    let mut st: Vec<Vec<u8>> = Vec::with_capacity(L);
+
+    for c in 0..R {
+        st.push(vec![c as u8]);
+    }
+
+    // 结束符不对应任何字节
+    st.push(Vec::new());
+
+    let mut reader = BitReader::new(data);
+    let mut result = Vec::new();
+    let mut codeword = reader.read_bits(W).expect("truncated lzw stream") as usize;
+
+    if codeword == R {
+        return result
+    }
+
+    let mut val = st[codeword].clone();
+
+    loop {
+        result.extend_from_slice(&val);
+
+        codeword = reader.read_bits(W).expect("truncated lzw stream") as usize;
+
+        if codeword == R {
+            break
+        }
+
+        // 编码刚好是下一个要加入表中的编码时，它的第一个字节就是 val 的第一个字节
+        let s = if codeword == st.len() {
+            let mut s = val.clone();
+            s.push(val[0]);
+            s
+        }
+        else {
+            st[codeword].clone()
+        };
+
+        if st.len() < L {
+            let mut entry = val;
+            entry.push(s[0]);
+            st.push(entry);
+        }
+
+        val = s;
+    }
+
+    result
+}
+
+#[test]
+fn test() {
+    extern crate rand;
+
+    let data = b"ABRABRACADABRABRABRA";
+    let compressed = compress(data);
+
+    // A B R AB RA C A D ABR ABRA BR A EOF，每个编码 12 位
+    let mut reader = BitReader::new(&compressed);
+    let codes: Vec<u64> = (0..13).map(|_| reader.read_bits(W).unwrap()).collect();
+    assert_eq!(codes, [65, 66, 82, 257, 259, 67, 65, 68, 260, 265, 258, 65, 256]);
+    assert_eq!(compressed.len(), (13 * W).div_ceil(8));
+    assert_eq!(expand(&compressed), data.to_vec());
+
+    // 编码刚好是下一个编码的情况
+    assert_eq!(expand(&compress(b"AAAAAAA")), b"AAAAAAA");
+
+    assert_eq!(expand(&compress(b"")), b"");
+
+    // 编译表填满之后不再加入新的键
+    let data: Vec<u8> = (0..20000).map(|_| rand::random::<u8>() % 8).collect();
+    let compressed = compress(&data);
+    assert!(compressed.len() < data.len());
+    assert_eq!(expand(&compressed), data);
+
+    let data: Vec<u8> = (0..2000).map(|_| rand::random()).collect();
+    assert_eq!(expand(&compress(&data)), data);
+}
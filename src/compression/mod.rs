@@ -0,0 +1,4 @@
+pub mod bit_io;
+pub mod huffman;
+pub mod lzw;
+pub mod run_length;
\ No newline at end of file
@@ -0,0 +1,79 @@
+// 游程编码
+// 把输入看作比特流，交替记录连续的 0 和连续的 1 的长度，从 0 开始
+// 每个长度占 8 位，超过 255 的游程之间插入长度为 0 的相反游程
+use super::bit_io::{BitReader, BitWriter};
+
+const LG_R: usize = 8;
+const R: usize = 1 << LG_R;
+
+pub fn compress(data: &[u8]) -> Vec<u8> {
+    let mut reader = BitReader::new(data);
+    let mut writer = BitWriter::new();
+    let mut run = 0;
+    let mut old = false;
+
+    while let Some(bit) = reader.read_bit() {
+        if bit != old {
+            writer.write_bits(run as u64, LG_R);
+            run = 0;
+            old = ! old;
+        }
+        else if run == R - 1 {
+            writer.write_bits(run as u64, LG_R);
+            writer.write_bits(0, LG_R);
+            run = 0;
+        }
+
+        run += 1;
+    }
+
+    if run > 0 {
+        writer.write_bits(run as u64, LG_R);
+    }
+
+    writer.finish()
+}
+
+// 长度都是整字节，输出也总是整字节
+pub fn expand(data: &[u8]) -> Vec<u8> {
+    let mut reader = BitReader::new(data);
+    let mut writer = BitWriter::new();
+    let mut bit = false;
+
+    while let Some(run) = reader.read_bits(LG_R) {
+        for _ in 0..run {
+            writer.write_bit(bit);
+        }
+
+        bit = ! bit;
+    }
+
+    writer.finish()
+}
+
+#[test]
+fn test() {
+    extern crate rand;
+
+    // 0000 0000 0000 0000 0111 1111 1111 1111 0000 0000 0000 0000 0000 0000 1111 1111
+    let data = [0x00, 0x00, 0x7F, 0xFF, 0x00, 0x00, 0x00, 0xFF];
+    let compressed = compress(&data);
+    assert_eq!(compressed, [17, 15, 24, 8]);
+    assert_eq!(expand(&compressed), data);
+
+    // 以 1 开始的比特流，先记录一个长度为 0 的游程
+    assert_eq!(compress(&[0xFF]), [0, 8]);
+    assert_eq!(expand(&[0, 8]), [0xFF]);
+
+    // 超过 255 的游程
+    let data = vec![0; 40];
+    let compressed = compress(&data);
+    assert_eq!(compressed, [255, 0, 65]);
+    assert_eq!(expand(&compressed), data);
+
+    assert!(compress(&[]).is_empty());
+    assert!(expand(&[]).is_empty());
+
+    let data: Vec<u8> = (0..1000).map(|_| rand::random()).collect();
+    assert_eq!(expand(&compress(&data)), data);
+}
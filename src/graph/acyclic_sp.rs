@@ -0,0 +1,137 @@
+use std::rc::Rc;
+use std::cmp::Ordering;
+use super::directed_edge::DirectedEdge;
+use super::edge::Weight;
+use super::edge_weighted_digraph::EdgeWeightedDigraph;
+use super::topological::Topological;
+
+// 无环加权有向图中的最短路径，按拓扑序放松顶点，边的权重可以为负
+pub struct AcyclicSP<W> {
+    edge_to: Vec<Option<Rc<DirectedEdge<W>>>>,
+    dist_to: Vec<Option<W>>,    // None 表示不可达
+}
+
+impl<W: Weight> AcyclicSP<W> {
+    // 图中有环时没有拓扑序，返回 None
+    pub fn new(g: &EdgeWeightedDigraph<W>, s: usize) -> Option<Self> {
+        let topological = Topological::new(&g.digraph());
+        let order = topological.order()?;
+
+        let mut this = AcyclicSP {
+            edge_to: Vec::with_capacity(g.v()),
+            dist_to: Vec::with_capacity(g.v()),
+        };
+
+        for _ in 0..g.v() {
+            this.edge_to.push(None);
+            this.dist_to.push(None);
+        }
+
+        this.dist_to[s] = Some(W::zero());
+
+        for v in order {
+            this.relax(g, *v);
+        }
+
+        Some(this)
+    }
+
+    // 顶点的松弛
+    fn relax(&mut self, g: &EdgeWeightedDigraph<W>, v: usize) {
+        // 拓扑序中起点之前的顶点和不可达的顶点不需要放松
+        let dist = match self.dist_to[v] {
+            Some(dist) => dist,
+            None => return,
+        };
+
+        for edge in g.adj(v) {
+            let w = edge.to();
+            let d = dist + edge.weight();
+            let shorter = match self.dist_to[w] {
+                Some(old) => d.total_cmp(&old) == Ordering::Less,
+                None => true,
+            };
+
+            if shorter {
+                self.dist_to[w] = Some(d);
+                self.edge_to[w] = Some(edge.clone());
+            }
+        }
+    }
+
+    // 起点到顶点 v 的距离，不可达时返回 None
+    pub fn dist_to(&self, v: usize) -> Option<W> {
+        self.dist_to[v]
+    }
+
+    pub fn has_path_to(&self, v: usize) -> bool {
+        self.dist_to[v].is_some()
+    }
+
+    pub fn path_to(&self, v: usize) -> Option<Vec<Rc<DirectedEdge<W>>>> {
+        if ! self.has_path_to(v) {
+            return None
+        }
+
+        let mut path = Vec::new();
+        let mut x = v;
+
+        while let Some(ref edge) = self.edge_to[x] {
+            path.push(edge.clone());
+            x = edge.from();
+        }
+
+        path.reverse();
+
+        Some(path)
+    }
+}
+
+#[test]
+fn test() {
+    let tiny_ewdag = [
+        (5, 4, 0.35), (4, 7, 0.37), (5, 7, 0.28), (5, 1, 0.32), (4, 0, 0.38),
+        (0, 2, 0.26), (3, 7, 0.39), (1, 3, 0.29), (7, 2, 0.34), (6, 2, 0.40),
+        (3, 6, 0.52), (6, 0, 0.58), (6, 4, 0.93),
+    ];
+
+    let mut g: EdgeWeightedDigraph<f64> = EdgeWeightedDigraph::with_capacity(8);
+
+    for &(v, w, weight) in tiny_ewdag.iter() {
+        g.add_edge(DirectedEdge::new(v, w, weight));
+    }
+
+    let sp = AcyclicSP::new(&g, 5).unwrap();
+    let dist = [0.73, 0.32, 0.62, 0.61, 0.35, 0.0, 1.13, 0.28];
+
+    for (v, d) in dist.iter().enumerate() {
+        assert!(sp.has_path_to(v));
+        assert!((sp.dist_to(v).unwrap() - d).abs() < 1e-6);
+    }
+
+    let path: Vec<usize> = sp.path_to(6).unwrap().iter().map(|e| e.to()).collect();
+    assert_eq!(path, [1, 3, 6]);
+
+    // 不可达的顶点
+    let sp = AcyclicSP::new(&g, 0).unwrap();
+
+    assert!(! sp.has_path_to(5));
+    assert_eq!(sp.dist_to(5), None);
+    assert_eq!(sp.path_to(5), None);
+
+    // 负的整数权重
+    let mut g = EdgeWeightedDigraph::with_capacity(3);
+    g.add_edge(DirectedEdge::new(0, 1, 2_i32));
+    g.add_edge(DirectedEdge::new(1, 2, -5));
+    g.add_edge(DirectedEdge::new(0, 2, 1));
+
+    let sp = AcyclicSP::new(&g, 0).unwrap();
+    assert_eq!(sp.dist_to(2), Some(-3));
+
+    // 有环的图
+    let mut g = EdgeWeightedDigraph::with_capacity(2);
+    g.add_edge(DirectedEdge::new(0, 1, 1.0));
+    g.add_edge(DirectedEdge::new(1, 0, 1.0));
+
+    assert!(AcyclicSP::new(&g, 0).is_none());
+}
@@ -0,0 +1,222 @@
+use std::rc::Rc;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use super::directed_edge::DirectedEdge;
+use super::edge::Weight;
+use super::edge_weighted_digraph::EdgeWeightedDigraph;
+use super::digraph::Digraph;
+use super::directed_cycle::DirectedCycle;
+
+// 最短路径 Bellman-Ford 算法（基于队列），边的权重可以为负
+pub struct BellmanFordSP<W> {
+    edge_to: Vec<Option<Rc<DirectedEdge<W>>>>,
+    dist_to: Vec<Option<W>>,                    // None 表示不可达
+    on_queue: Vec<bool>,                        // 顶点是否在队列中
+    queue: VecDeque<usize>,                     // 正在被放松的顶点
+    cost: usize,                                // relax() 的调用次数
+    cycle: Option<Vec<Rc<DirectedEdge<W>>>>,    // 负权重环
+}
+
+impl<W: Weight> BellmanFordSP<W> {
+    pub fn new(g: &EdgeWeightedDigraph<W>, s: usize) -> Self {
+        let mut this = BellmanFordSP {
+            edge_to: Vec::with_capacity(g.v()),
+            dist_to: Vec::with_capacity(g.v()),
+            on_queue: Vec::with_capacity(g.v()),
+            queue: VecDeque::new(),
+            cost: 0,
+            cycle: None,
+        };
+
+        for _ in 0..g.v() {
+            this.edge_to.push(None);
+            this.dist_to.push(None);
+            this.on_queue.push(false);
+        }
+
+        this.dist_to[s] = Some(W::zero());
+        this.queue.push_back(s);
+        this.on_queue[s] = true;
+
+        while ! this.has_negative_cycle() {
+            match this.queue.pop_front() {
+                Some(v) => {
+                    this.on_queue[v] = false;
+                    this.relax(g, v);
+                },
+                None => break,
+            }
+        }
+
+        this
+    }
+
+    // 顶点的松弛
+    fn relax(&mut self, g: &EdgeWeightedDigraph<W>, v: usize) {
+        // 队列中的顶点都是可达的
+        let dist = self.dist_to[v].unwrap();
+
+        for edge in g.adj(v) {
+            let w = edge.to();
+            let d = dist + edge.weight();
+            let shorter = match self.dist_to[w] {
+                Some(old) => d.total_cmp(&old) == Ordering::Less,
+                None => true,
+            };
+
+            if shorter {
+                self.dist_to[w] = Some(d);
+                self.edge_to[w] = Some(edge.clone());
+
+                if ! self.on_queue[w] {
+                    self.queue.push_back(w);
+                    self.on_queue[w] = true;
+                }
+            }
+
+            // 每调用 V 次 relax() 检查一次最短路径树中是否有环
+            self.cost += 1;
+
+            if self.cost % g.v() == 0 {
+                self.find_negative_cycle(g);
+
+                if self.has_negative_cycle() {
+                    return
+                }
+            }
+        }
+    }
+
+    // 最短路径树中的环必然是负权重环
+    fn find_negative_cycle(&mut self, g: &EdgeWeightedDigraph<W>) {
+        let mut spt = Digraph::with_capacity(g.v());
+
+        for edge in &self.edge_to {
+            if let Some(ref edge) = *edge {
+                spt.add_edge(edge.from(), edge.to());
+            }
+        }
+
+        let finder = DirectedCycle::new(&spt);
+
+        // 树中每个顶点只有一条指向它的边，即 edge_to 中的边
+        if let Some(cycle) = finder.cycle() {
+            let edges = cycle[1..].iter()
+                .map(|w| self.edge_to[*w].clone().unwrap())
+                .collect();
+
+            self.cycle = Some(edges);
+        }
+    }
+
+    pub fn has_negative_cycle(&self) -> bool {
+        self.cycle.is_some()
+    }
+
+    pub fn negative_cycle(&self) -> Option<&Vec<Rc<DirectedEdge<W>>>> {
+        self.cycle.as_ref()
+    }
+
+    // 起点到顶点 v 的距离，不可达时返回 None
+    pub fn dist_to(&self, v: usize) -> Option<W> {
+        self.dist_to[v]
+    }
+
+    pub fn has_path_to(&self, v: usize) -> bool {
+        self.dist_to[v].is_some()
+    }
+
+    // 存在负权重环时，最短路径没有意义，返回 None
+    pub fn path_to(&self, v: usize) -> Option<Vec<Rc<DirectedEdge<W>>>> {
+        if self.has_negative_cycle() || ! self.has_path_to(v) {
+            return None
+        }
+
+        let mut path = Vec::new();
+        let mut x = v;
+
+        while let Some(ref edge) = self.edge_to[x] {
+            path.push(edge.clone());
+            x = edge.from();
+        }
+
+        path.reverse();
+
+        Some(path)
+    }
+}
+
+#[test]
+fn test() {
+    let tiny_ewdn = [
+        (4, 5, 0.35), (5, 4, 0.35), (4, 7, 0.37), (5, 7, 0.28), (7, 5, 0.28),
+        (5, 1, 0.32), (0, 4, 0.38), (0, 2, 0.26), (7, 3, 0.39), (1, 3, 0.29),
+        (2, 7, 0.34), (6, 2, -1.20), (3, 6, 0.52), (6, 0, -1.40), (6, 4, -1.25),
+    ];
+
+    let mut g: EdgeWeightedDigraph<f32> = EdgeWeightedDigraph::with_capacity(8);
+
+    for &(v, w, weight) in tiny_ewdn.iter() {
+        g.add_edge(DirectedEdge::new(v, w, weight));
+    }
+
+    let sp = BellmanFordSP::new(&g, 0);
+    let dist = [0.0, 0.93, 0.26, 0.99, 0.26, 0.61, 1.51, 0.60];
+
+    assert!(! sp.has_negative_cycle());
+    assert_eq!(sp.negative_cycle(), None);
+
+    for (v, d) in dist.iter().enumerate() {
+        assert!(sp.has_path_to(v));
+        assert!((sp.dist_to(v).unwrap() - d).abs() < 1e-6);
+    }
+
+    let path: Vec<usize> = sp.path_to(1).unwrap().iter().map(|e| e.to()).collect();
+    assert_eq!(path, [2, 7, 3, 6, 4, 5, 1]);
+
+    // 整数权重，不可达的顶点
+    let mut g = EdgeWeightedDigraph::with_capacity(4);
+    g.add_edge(DirectedEdge::new(0, 1, 4_i64));
+    g.add_edge(DirectedEdge::new(0, 2, 1));
+    g.add_edge(DirectedEdge::new(2, 1, -2));
+
+    let sp = BellmanFordSP::new(&g, 0);
+
+    assert_eq!(sp.dist_to(1), Some(-1));
+    assert_eq!(sp.dist_to(3), None);
+    assert_eq!(sp.path_to(3), None);
+}
+
+#[test]
+fn test_negative_cycle() {
+    let tiny_ewdnc = [
+        (4, 5, 0.35), (5, 4, -0.66), (4, 7, 0.37), (5, 7, 0.28), (7, 5, 0.28),
+        (5, 1, 0.32), (0, 4, 0.38), (0, 2, 0.26), (7, 3, 0.39), (1, 3, 0.29),
+        (2, 7, 0.34), (6, 2, 0.40), (3, 6, 0.52), (6, 0, 0.58), (6, 4, 0.93),
+    ];
+
+    let mut g: EdgeWeightedDigraph<f32> = EdgeWeightedDigraph::with_capacity(8);
+
+    for &(v, w, weight) in tiny_ewdnc.iter() {
+        g.add_edge(DirectedEdge::new(v, w, weight));
+    }
+
+    let sp = BellmanFordSP::new(&g, 0);
+
+    assert!(sp.has_negative_cycle());
+    assert_eq!(sp.path_to(1), None);
+
+    let cycle = sp.negative_cycle().unwrap();
+    let weight: f32 = cycle.iter().map(|e| e.weight()).sum();
+
+    assert!(weight < 0.0);
+
+    // 首尾相连
+    for i in 0..cycle.len() {
+        assert_eq!(cycle[i].to(), cycle[(i + 1) % cycle.len()].from());
+    }
+
+    let mut vertices: Vec<usize> = cycle.iter().map(|e| e.from()).collect();
+    vertices.sort();
+    assert_eq!(vertices, [4, 5]);
+}
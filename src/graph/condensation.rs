@@ -0,0 +1,88 @@
+use super::digraph::Digraph;
+
+// 有向图的缩点，将每个强连通分量收缩为一个顶点，得到一幅有向无环图
+// id[v] 为顶点 v 所属的强连通分量，count 为强连通分量数量
+pub fn condensation(g: &Digraph, id: &[usize], count: usize) -> Digraph {
+    let mut dag = Digraph::with_capacity(count);
+    let mut members = vec![Vec::new(); count];
+
+    for v in 0..g.v() {
+        members[id[v]].push(v);
+    }
+
+    // 逐个分量加边，marked[j] == i 表示边 i -> j 已经加入，用来忽略平行边
+    let mut marked = vec![count; count];
+
+    for (i, vertices) in members.iter().enumerate() {
+        for &v in vertices {
+            for &w in g.adj(v) {
+                let j = id[w];
+
+                // 忽略分量内部的边
+                if i != j && marked[j] != i {
+                    marked[j] = i;
+                    dag.add_edge(i, j);
+                }
+            }
+        }
+    }
+
+    dag
+}
+
+#[test]
+fn test() {
+    use super::kosaraju_scc::KosarajuSCC;
+    use super::tarjan_scc::TarjanSCC;
+    use super::topological::Topological;
+
+    let tiny_dg = [
+        (4, 2), (2, 3), (3, 2), (6, 0), (0, 1), (2, 0), (11, 12),
+        (12, 9), (9, 10), (9, 11), (7, 9), (10, 12), (11, 4), (4, 3),
+        (3, 5), (6, 8), (8, 6), (5, 4), (0, 5), (6, 4), (6, 9), (7, 6),
+    ];
+
+    let mut g = Digraph::with_capacity(13);
+
+    for &(v, w) in tiny_dg.iter() {
+        g.add_edge(v, w);
+    }
+
+    let scc = KosarajuSCC::new(&g);
+    let dag = scc.condensation(&g);
+
+    assert_eq!(dag.v(), 5);
+    assert_eq!(dag.e(), 6);
+    assert!(Topological::new(&dag).is_dag());
+
+    // {0, 2, 3, 4, 5} -> {1}
+    assert!(dag.adj(scc.id()[0]).contains(&scc.id()[1]));
+    // {7} -> {6, 8}
+    assert!(dag.adj(scc.id()[7]).contains(&scc.id()[6]));
+
+    let tarjan = TarjanSCC::new(&g);
+    let dag = tarjan.condensation(&g);
+
+    assert_eq!(dag.v(), 5);
+    assert_eq!(dag.e(), 6);
+    assert!(Topological::new(&dag).is_dag());
+
+    // 两个稠密的分量之间有大量平行边，只保留一条
+    let mut g = Digraph::with_capacity(20);
+
+    for v in 0..10 {
+        g.add_edge(v, (v + 1) % 10);
+        g.add_edge(10 + v, 10 + (v + 1) % 10);
+
+        for w in 10..20 {
+            g.add_edge(v, w);
+        }
+    }
+
+    let scc = KosarajuSCC::new(&g);
+    let dag = scc.condensation(&g);
+
+    assert_eq!(dag.v(), 2);
+    assert_eq!(dag.e(), 1);
+    assert_eq!(dag.adj(scc.id()[0]), &vec![scc.id()[10]]);
+}
@@ -0,0 +1,78 @@
+use super::digraph::Digraph;
+
+// 有向图中基于深度优先搜索的顶点排序
+pub struct DepthFirstOrder {
+    marked: Vec<bool>,
+    pre: Vec<usize>,        // 前序，递归调用之前将顶点加入
+    post: Vec<usize>,       // 后序，递归调用之后将顶点加入
+}
+
+impl DepthFirstOrder {
+    pub fn new(g: &Digraph) -> Self {
+        let mut this = DepthFirstOrder {
+            marked: Vec::with_capacity(g.v()),
+            pre: Vec::with_capacity(g.v()),
+            post: Vec::with_capacity(g.v()),
+        };
+
+        for _ in 0..g.v() {
+            this.marked.push(false);
+        }
+
+        for v in 0..g.v() {
+            if ! this.marked[v] {
+                this.dfs(g, v);
+            }
+        }
+
+        this
+    }
+
+    fn dfs(&mut self, g: &Digraph, v: usize) {
+        self.marked[v] = true;
+        self.pre.push(v);
+
+        for w in g.adj(v) {
+            if ! self.marked[*w] {
+                self.dfs(g, *w);
+            }
+        }
+
+        self.post.push(v);
+    }
+
+    // 前序
+    pub fn pre(&self) -> &Vec<usize> {
+        &self.pre
+    }
+
+    // 后序
+    pub fn post(&self) -> &Vec<usize> {
+        &self.post
+    }
+
+    // 逆后序
+    pub fn reverse_post(&self) -> Vec<usize> {
+        self.post.iter().rev().cloned().collect()
+    }
+}
+
+#[test]
+fn test() {
+    let tiny_dag = [
+        (2, 3), (0, 6), (0, 1), (2, 0), (11, 12), (9, 12), (9, 10), (9, 11),
+        (3, 5), (8, 7), (5, 4), (0, 5), (6, 4), (6, 9), (7, 6),
+    ];
+
+    let mut g = Digraph::with_capacity(13);
+
+    for &(v, w) in tiny_dag.iter() {
+        g.add_edge(v, w);
+    }
+
+    let order = DepthFirstOrder::new(&g);
+
+    assert_eq!(order.pre(), &[0, 6, 4, 9, 12, 10, 11, 1, 5, 2, 3, 7, 8]);
+    assert_eq!(order.post(), &[4, 12, 10, 11, 9, 6, 1, 5, 0, 3, 2, 7, 8]);
+    assert_eq!(order.reverse_post(), vec![8, 7, 2, 3, 0, 5, 1, 6, 9, 11, 10, 12, 4]);
+}
@@ -0,0 +1,119 @@
+use std::fmt;
+
+// 有向图
+pub struct Digraph {
+    v: usize,
+    e: usize,
+    adj: Vec<Vec<usize>>,
+    indegree: Vec<usize>,
+}
+
+impl Digraph {
+    // 给定顶点数量，初始化有向图
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut this = Digraph {
+            v: capacity,
+            e: 0,
+            adj: Vec::with_capacity(capacity),
+            indegree: Vec::with_capacity(capacity),
+        };
+
+        for _ in 0..capacity {
+            this.adj.push(Vec::new());
+            this.indegree.push(0);
+        }
+
+        this
+    }
+
+    // 顶点数量
+    pub fn v(&self) -> usize {
+        self.v
+    }
+
+    // 边数量
+    pub fn e(&self) -> usize {
+        self.e
+    }
+
+    // 增加一条由 v 指向 w 的边
+    pub fn add_edge(&mut self, v: usize, w: usize) {
+        self.adj[v].push(w);
+        self.indegree[w] += 1;
+        self.e += 1;
+    }
+
+    // 由顶点 v 指出的边所连接的顶点
+    pub fn adj(&self, v: usize) -> &Vec<usize> {
+        &self.adj[v]
+    }
+
+    // 出度
+    pub fn out_degree(&self, v: usize) -> usize {
+        self.adj[v].len()
+    }
+
+    // 入度
+    pub fn in_degree(&self, v: usize) -> usize {
+        self.indegree[v]
+    }
+
+    // 反向图，所有边的方向取反
+    pub fn reverse(&self) -> Digraph {
+        let mut r = Digraph::with_capacity(self.v());
+
+        for v in 0..self.v() {
+            for w in self.adj(v) {
+                r.add_edge(*w, v);
+            }
+        }
+
+        r
+    }
+}
+
+
+impl fmt::Debug for Digraph {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut string = String::new();
+
+        string.push_str(fmt::format(format_args!("{} vertices, {} edges \n", self.v(), self.e())).as_str());
+
+        for v in 0..self.v() {
+            string.push_str(fmt::format(format_args!("{}: {:?} \n", v, self.adj(v))).as_str());
+        }
+
+        write!(f, "{}", string)
+    }
+}
+
+#[test]
+fn test() {
+    let tiny_dg = [
+        (4, 2), (2, 3), (3, 2), (6, 0), (0, 1), (2, 0), (11, 12),
+        (12, 9), (9, 10), (9, 11), (7, 9), (10, 12), (11, 4), (4, 3),
+        (3, 5), (6, 8), (8, 6), (5, 4), (0, 5), (6, 4), (6, 9), (7, 6),
+    ];
+
+    let mut g = Digraph::with_capacity(13);
+
+    for &(v, w) in tiny_dg.iter() {
+        g.add_edge(v, w);
+    }
+
+    assert_eq!(g.v(), 13);
+    assert_eq!(g.e(), 22);
+    assert_eq!(g.adj(6), &[0, 8, 4, 9]);
+    assert_eq!(g.out_degree(6), 4);
+    assert_eq!(g.in_degree(6), 2);
+
+    let r = g.reverse();
+
+    assert_eq!(r.v(), 13);
+    assert_eq!(r.e(), 22);
+    assert_eq!(r.adj(6), &[7, 8]);
+    assert_eq!(r.out_degree(6), 2);
+    assert_eq!(r.in_degree(6), 4);
+
+    assert!(format!("{:?}", g).starts_with("13 vertices, 22 edges \n"));
+}
@@ -0,0 +1,166 @@
+use std::rc::Rc;
+use std::cmp::Ordering;
+use super::directed_edge::DirectedEdge;
+use super::edge::Weight;
+use super::edge_weighted_digraph::EdgeWeightedDigraph;
+use super::super::queue::index_binary_heap::IndexBinaryHeap;
+
+// 实现最小索引优先队列，重写 PartialOrd
+struct Key<W>(W);
+
+impl<W: Weight> PartialEq for Key<W> {
+    fn eq(&self, other: &Key<W>) -> bool {
+        self.0.total_cmp(&other.0) == Ordering::Equal
+    }
+}
+
+impl<W: Weight> PartialOrd for Key<W> {
+    fn partial_cmp(&self, other: &Key<W>) -> Option<Ordering> {
+        Some(other.0.total_cmp(&self.0))
+    }
+}
+
+// 最短路径 Dijkstra 算法，边的权重不能为负
+pub struct DijkstraSP<W> {
+    edge_to: Vec<Option<Rc<DirectedEdge<W>>>>,  // 最短路径树中指向顶点的边
+    dist_to: Vec<Option<W>>,                    // 起点到顶点的距离，None 表示不可达
+    pq: IndexBinaryHeap<Key<W>>,                // 最小索引优先队列
+}
+
+impl<W: Weight> DijkstraSP<W> {
+    pub fn new(g: &EdgeWeightedDigraph<W>, s: usize) -> Self {
+        for edge in g.edges() {
+            let negative = edge.weight().total_cmp(&W::zero()) == Ordering::Less;
+            assert!(! negative, "edge {}->{} has negative weight", edge.from(), edge.to());
+        }
+
+        let mut this = DijkstraSP {
+            edge_to: Vec::with_capacity(g.v()),
+            dist_to: Vec::with_capacity(g.v()),
+            pq: IndexBinaryHeap::with_capacity(g.v()),
+        };
+
+        for _ in 0..g.v() {
+            this.edge_to.push(None);
+            this.dist_to.push(None);
+        }
+
+        this.dist_to[s] = Some(W::zero());
+        this.pq.put(s, Key(W::zero()));
+
+        while ! this.pq.is_empty() {
+            let v = this.pq.pop();
+            this.relax(g, v);
+        }
+
+        this
+    }
+
+    // 顶点的松弛
+    fn relax(&mut self, g: &EdgeWeightedDigraph<W>, v: usize) {
+        // 从队列中取出的顶点都是可达的
+        let dist = self.dist_to[v].unwrap();
+
+        for edge in g.adj(v) {
+            let w = edge.to();
+            let d = dist + edge.weight();
+
+            let shorter = match self.dist_to[w] {
+                Some(old) => d.total_cmp(&old) == Ordering::Less,
+                None => true,
+            };
+
+            if shorter {
+                self.dist_to[w] = Some(d);
+                self.edge_to[w] = Some(edge.clone());
+                // 有则更新，无则添加
+                self.pq.put(w, Key(d));
+            }
+        }
+    }
+
+    // 起点到顶点 v 的距离，不可达时返回 None
+    pub fn dist_to(&self, v: usize) -> Option<W> {
+        self.dist_to[v]
+    }
+
+    pub fn has_path_to(&self, v: usize) -> bool {
+        self.dist_to[v].is_some()
+    }
+
+    pub fn path_to(&self, v: usize) -> Option<Vec<Rc<DirectedEdge<W>>>> {
+        if ! self.has_path_to(v) {
+            return None
+        }
+
+        let mut path = Vec::new();
+        let mut x = v;
+
+        while let Some(ref edge) = self.edge_to[x] {
+            path.push(edge.clone());
+            x = edge.from();
+        }
+
+        path.reverse();
+
+        Some(path)
+    }
+}
+
+#[test]
+fn test() {
+    let tiny_ewd = [
+        (4, 5, 0.35), (5, 4, 0.35), (4, 7, 0.37), (5, 7, 0.28), (7, 5, 0.28),
+        (5, 1, 0.32), (0, 4, 0.38), (0, 2, 0.26), (7, 3, 0.39), (1, 3, 0.29),
+        (2, 7, 0.34), (6, 2, 0.40), (3, 6, 0.52), (6, 0, 0.58), (6, 4, 0.93),
+    ];
+
+    let mut g: EdgeWeightedDigraph<f64> = EdgeWeightedDigraph::with_capacity(8);
+
+    for &(v, w, weight) in tiny_ewd.iter() {
+        g.add_edge(DirectedEdge::new(v, w, weight));
+    }
+
+    let sp = DijkstraSP::new(&g, 0);
+    let dist = [0.0, 1.05, 0.26, 0.99, 0.38, 0.73, 1.51, 0.60];
+
+    for (v, d) in dist.iter().enumerate() {
+        assert!(sp.has_path_to(v));
+        assert!((sp.dist_to(v).unwrap() - d).abs() < 1e-6);
+    }
+
+    let path: Vec<usize> = sp.path_to(6).unwrap().iter().map(|e| e.to()).collect();
+    assert_eq!(path, [2, 7, 3, 6]);
+    assert_eq!(sp.path_to(0).unwrap().len(), 0);
+
+    // 不可达的顶点
+    let mut g = EdgeWeightedDigraph::with_capacity(3);
+    g.add_edge(DirectedEdge::new(0, 1, 1.0));
+
+    let sp = DijkstraSP::new(&g, 0);
+
+    assert!(! sp.has_path_to(2));
+    assert_eq!(sp.dist_to(2), None);
+    assert_eq!(sp.path_to(2), None);
+
+    // 整数权重
+    let mut g = EdgeWeightedDigraph::with_capacity(4);
+    g.add_edge(DirectedEdge::new(0, 1, 5_u32));
+    g.add_edge(DirectedEdge::new(0, 2, 1));
+    g.add_edge(DirectedEdge::new(2, 1, 2));
+    g.add_edge(DirectedEdge::new(1, 3, 1));
+
+    let sp = DijkstraSP::new(&g, 0);
+
+    assert_eq!(sp.dist_to(3), Some(4));
+    assert_eq!(sp.path_to(3).unwrap().iter().map(|e| e.to()).collect::<Vec<_>>(), [2, 1, 3]);
+}
+
+#[test]
+#[should_panic]
+fn test_negative_weight() {
+    let mut g = EdgeWeightedDigraph::with_capacity(2);
+    g.add_edge(DirectedEdge::new(0, 1, -1.0_f32));
+
+    DijkstraSP::new(&g, 0);
+}
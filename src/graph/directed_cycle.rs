@@ -0,0 +1,121 @@
+use super::digraph::Digraph;
+
+// 有向环检测
+pub struct DirectedCycle {
+    marked: Vec<bool>,
+    edge_to: Vec<Option<usize>>,
+    on_stack: Vec<bool>,            // 递归调用栈上的顶点
+    cycle: Option<Vec<usize>>,      // 有向环中的顶点（首尾相同）
+}
+
+impl DirectedCycle {
+    pub fn new(g: &Digraph) -> Self {
+        let mut this = DirectedCycle {
+            marked: Vec::with_capacity(g.v()),
+            edge_to: Vec::with_capacity(g.v()),
+            on_stack: Vec::with_capacity(g.v()),
+            cycle: None,
+        };
+
+        for _ in 0..g.v() {
+            this.marked.push(false);
+            this.edge_to.push(None);
+            this.on_stack.push(false);
+        }
+
+        for v in 0..g.v() {
+            if ! this.marked[v] && this.cycle.is_none() {
+                this.dfs(g, v);
+            }
+        }
+
+        this
+    }
+
+    fn dfs(&mut self, g: &Digraph, v: usize) {
+        self.marked[v] = true;
+        self.on_stack[v] = true;
+
+        for w in g.adj(v) {
+            if self.cycle.is_some() {
+                return
+            }
+
+            if ! self.marked[*w] {
+                self.edge_to[*w] = Some(v);
+                self.dfs(g, *w);
+            }
+            else if self.on_stack[*w] {
+                // 沿 edge_to 从 v 回溯到 w，得到环 w -> ... -> v -> w
+                let mut cycle = Vec::new();
+                let mut x = v;
+
+                while x != *w {
+                    cycle.push(x);
+                    x = self.edge_to[x].unwrap();
+                }
+
+                cycle.push(*w);
+                cycle.reverse();
+                cycle.push(*w);
+
+                self.cycle = Some(cycle);
+            }
+        }
+
+        self.on_stack[v] = false;
+    }
+
+    pub fn has_cycle(&self) -> bool {
+        self.cycle.is_some()
+    }
+
+    pub fn cycle(&self) -> Option<&Vec<usize>> {
+        self.cycle.as_ref()
+    }
+}
+
+#[test]
+fn test() {
+    let tiny_dg = [
+        (4, 2), (2, 3), (3, 2), (6, 0), (0, 1), (2, 0), (11, 12),
+        (12, 9), (9, 10), (9, 11), (7, 9), (10, 12), (11, 4), (4, 3),
+        (3, 5), (6, 8), (8, 6), (5, 4), (0, 5), (6, 4), (6, 9), (7, 6),
+    ];
+
+    let mut g = Digraph::with_capacity(13);
+
+    for &(v, w) in tiny_dg.iter() {
+        g.add_edge(v, w);
+    }
+
+    let finder = DirectedCycle::new(&g);
+
+    assert!(finder.has_cycle());
+    assert_eq!(finder.cycle(), Some(&vec![2, 3, 2]));
+
+    // 环上相邻的顶点之间都有边
+    let cycle = finder.cycle().unwrap();
+
+    for i in 0..cycle.len() - 1 {
+        assert!(g.adj(cycle[i]).contains(&cycle[i + 1]));
+    }
+
+    // 自环
+    let mut g = Digraph::with_capacity(2);
+    g.add_edge(0, 1);
+    g.add_edge(1, 1);
+
+    assert_eq!(DirectedCycle::new(&g).cycle(), Some(&vec![1, 1]));
+
+    // 无环
+    let mut g = Digraph::with_capacity(3);
+    g.add_edge(0, 1);
+    g.add_edge(1, 2);
+    g.add_edge(0, 2);
+
+    let finder = DirectedCycle::new(&g);
+
+    assert!(! finder.has_cycle());
+    assert_eq!(finder.cycle(), None);
+}
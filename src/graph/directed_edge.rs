@@ -0,0 +1,46 @@
+use super::edge::Weight;
+
+// 加权有向图中的边
+#[derive(Debug, PartialEq)]
+pub struct DirectedEdge<W> {
+    v: usize,
+    w: usize,
+    weight: W,
+}
+
+impl<W: Weight> DirectedEdge<W> {
+    pub fn new(v: usize, w: usize, weight: W) -> Self {
+        DirectedEdge {v, w, weight}
+    }
+
+    // 权重
+    pub fn weight(&self) -> W {
+        self.weight
+    }
+
+    // 边的起点
+    pub fn from(&self) -> usize {
+        self.v
+    }
+
+    // 边的终点
+    pub fn to(&self) -> usize {
+        self.w
+    }
+}
+
+
+#[test]
+fn test() {
+    let edge = DirectedEdge::new(4, 5, 0.35);
+    let edge2 = DirectedEdge::new(6, 0, -1.4);
+
+    assert_eq!(edge.weight(), 0.35);
+    assert_eq!(edge.from(), 4);
+    assert_eq!(edge.to(), 5);
+    assert_eq!(edge2.weight(), -1.4);
+    assert!(edge != edge2);
+
+    let edge = DirectedEdge::new(0, 1, -3_i64);
+    assert_eq!(edge.weight(), -3);
+}
@@ -1,22 +1,64 @@
 use std::cmp::Ordering;
-use std::f32;
+use std::ops::Add;
+
+// 边的权重，需要满足全序关系并且可以相加
+// 浮点数按 IEEE 754 的 totalOrder 排序，负数和 NaN 也能得到确定的顺序
+pub trait Weight: Copy + Add<Output = Self> {
+    // 零值，用于累加权重
+    fn zero() -> Self;
+
+    // 全序比较
+    fn total_cmp(&self, other: &Self) -> Ordering;
+}
+
+macro_rules! integer_weight {
+    ($($t:ty)*) => ($(
+        impl Weight for $t {
+            fn zero() -> Self {
+                0
+            }
+
+            fn total_cmp(&self, other: &Self) -> Ordering {
+                self.cmp(other)
+            }
+        }
+    )*)
+}
+
+integer_weight! { i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize }
+
+macro_rules! float_weight {
+    ($($t:ty)*) => ($(
+        impl Weight for $t {
+            fn zero() -> Self {
+                0.0
+            }
+
+            fn total_cmp(&self, other: &Self) -> Ordering {
+                <$t>::total_cmp(self, other)
+            }
+        }
+    )*)
+}
+
+float_weight! { f32 f64 }
 
 // 加权图中的边
-#[derive(Debug, Eq, PartialEq)]
-pub struct Edge {
+#[derive(Debug)]
+pub struct Edge<W> {
     v: usize,
     w: usize,
-    weight: u32,
+    weight: W,
 }
 
-impl Edge {
-    pub fn new(v: usize, w: usize, weight: f32) -> Self {
-        Edge {v, w, weight: weight.to_bits()}
+impl<W: Weight> Edge<W> {
+    pub fn new(v: usize, w: usize, weight: W) -> Self {
+        Edge {v, w, weight}
     }
 
     // 权重
-    pub fn weight(&self) -> f32 {
-        f32::from_bits(self.weight)
+    pub fn weight(&self) -> W {
+        self.weight
     }
 
     // 其中的1个顶点
@@ -38,16 +80,24 @@ impl Edge {
     }
 }
 
+impl<W: Weight> PartialEq for Edge<W> {
+    fn eq(&self, other: &Edge<W>) -> bool {
+        self.v == other.v && self.w == other.w && self.weight.total_cmp(&other.weight) == Ordering::Equal
+    }
+}
+
+impl<W: Weight> Eq for Edge<W> {}
+
 // 在 std::collections::BinaryHeap 中实现由小到大排序的优先队列
-impl Ord for Edge {
-    fn cmp(&self, other: &Edge) -> Ordering {
-        other.weight.cmp(&self.weight)
+impl<W: Weight> Ord for Edge<W> {
+    fn cmp(&self, other: &Edge<W>) -> Ordering {
+        other.weight.total_cmp(&self.weight)
     }
 }
 
-impl PartialOrd for Edge {
-    fn partial_cmp(&self, other: &Edge) -> Option<Ordering> {
-        Some(self.cmp(&other))
+impl<W: Weight> PartialOrd for Edge<W> {
+    fn partial_cmp(&self, other: &Edge<W>) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
@@ -69,4 +119,30 @@ fn test() {
     assert_eq!(edge.partial_cmp(&edge2), Some(Ordering::Greater));
     assert_eq!(edge2.partial_cmp(&edge), Some(Ordering::Less));
 
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_weight() {
+    // 负数权重
+    let edge = Edge::new(0, 1, -0.5f32);
+    let edge2 = Edge::new(1, 2, 0.25f32);
+    let edge3 = Edge::new(2, 3, -2.0f32);
+
+    assert!(edge > edge2);
+    assert!(edge3 > edge);
+
+    // NaN 排在所有数字之后
+    let nan = Edge::new(0, 1, f64::NAN);
+    let inf = Edge::new(0, 1, f64::INFINITY);
+
+    assert!(nan < inf);
+    assert_eq!(nan, Edge::new(0, 1, f64::NAN));
+
+    // 整数权重
+    let edge = Edge::new(0, 1, -3i64);
+    let edge2 = Edge::new(1, 2, 7i64);
+
+    assert!(edge > edge2);
+    assert_eq!(edge.weight() + edge2.weight(), 4);
+    assert_eq!(i64::zero(), 0);
+}
@@ -0,0 +1,93 @@
+use std::rc::Rc;
+use super::directed_edge::DirectedEdge;
+use super::digraph::Digraph;
+use super::edge::Weight;
+
+
+// 加权有向图
+pub struct EdgeWeightedDigraph<W> {
+    v: usize,
+    e: usize,
+    adj: Vec<Vec<Rc<DirectedEdge<W>>>>,
+}
+
+impl<W: Weight> EdgeWeightedDigraph<W> {
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut this = EdgeWeightedDigraph {
+            v: capacity,
+            e: 0,
+            adj: Vec::with_capacity(capacity),
+        };
+
+        for _ in 0..capacity {
+            this.adj.push(Vec::new());
+        }
+
+        this
+    }
+
+    pub fn v(&self) -> usize {
+        self.v
+    }
+
+    pub fn e(&self) -> usize {
+        self.e
+    }
+
+    pub fn add_edge(&mut self, edge: DirectedEdge<W>) {
+        let v = edge.from();
+
+        self.adj[v].push(Rc::new(edge));
+        self.e += 1;
+    }
+
+    // 由顶点 v 指出的边
+    pub fn adj(&self, v: usize) -> &Vec<Rc<DirectedEdge<W>>> {
+        &self.adj[v]
+    }
+
+    pub fn edges(&self) -> Vec<Rc<DirectedEdge<W>>> {
+        let mut edges = Vec::with_capacity(self.e());
+
+        for v in 0..self.v() {
+            for edge in self.adj(v) {
+                edges.push(edge.clone());
+            }
+        }
+
+        edges
+    }
+
+    // 去掉权重后的有向图，用于环检测和拓扑排序
+    pub fn digraph(&self) -> Digraph {
+        let mut g = Digraph::with_capacity(self.v());
+
+        for edge in self.edges() {
+            g.add_edge(edge.from(), edge.to());
+        }
+
+        g
+    }
+}
+
+
+#[test]
+fn test() {
+    let tiny_ewd = [
+        (4, 5, 0.35), (5, 4, 0.35), (4, 7, 0.37), (5, 7, 0.28), (7, 5, 0.28),
+        (5, 1, 0.32), (0, 4, 0.38), (0, 2, 0.26), (7, 3, 0.39), (1, 3, 0.29),
+        (2, 7, 0.34), (6, 2, 0.40), (3, 6, 0.52), (6, 0, 0.58), (6, 4, 0.93),
+    ];
+
+    let mut g: EdgeWeightedDigraph<f64> = EdgeWeightedDigraph::with_capacity(8);
+
+    for &(v, w, weight) in tiny_ewd.iter() {
+        g.add_edge(DirectedEdge::new(v, w, weight));
+    }
+
+    assert_eq!(g.v(), 8);
+    assert_eq!(g.e(), 15);
+    assert_eq!(g.adj(6).len(), 3);
+    assert_eq!(g.edges().len(), 15);
+    assert_eq!(g.digraph().adj(6), &[2, 0, 4]);
+}
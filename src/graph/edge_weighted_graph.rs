@@ -1,15 +1,15 @@
 use std::rc::Rc;
-use super::edge::Edge;
+use super::edge::{Edge, Weight};
 
 
 // 加权无向图
-pub struct EdgeWeightedGraph {
+pub struct EdgeWeightedGraph<W> {
     v: usize,
     e: usize,
-    adj: Vec<Vec<Rc<Edge>>>,
+    adj: Vec<Vec<Rc<Edge<W>>>>,
 }
 
-impl EdgeWeightedGraph {
+impl<W: Weight> EdgeWeightedGraph<W> {
     pub fn with_capacity(capacity: usize) -> Self {
         let mut this = EdgeWeightedGraph {
             v: capacity,
@@ -32,7 +32,7 @@ impl EdgeWeightedGraph {
         self.e
     }
 
-    pub fn add_edge(&mut self, edge: Edge) {
+    pub fn add_edge(&mut self, edge: Edge<W>) {
         let edge = Rc::new(edge);
         let v = edge.either();
 
@@ -43,11 +43,11 @@ impl EdgeWeightedGraph {
         }
     }
 
-    pub fn adj(&self, v: usize) -> &Vec<Rc<Edge>> {
+    pub fn adj(&self, v: usize) -> &Vec<Rc<Edge<W>>> {
         &self.adj[v]
     }
 
-    pub fn edges(&self) -> Vec<Rc<Edge>> {
+    pub fn edges(&self) -> Vec<Rc<Edge<W>>> {
         let mut edges = Vec::with_capacity(self.e());
 
         for v in 0..self.v() {
@@ -0,0 +1,105 @@
+use super::digraph::Digraph;
+use super::depth_first_order::DepthFirstOrder;
+use super::condensation::condensation;
+
+// 强连通分量 Kosaraju-Sharir 算法
+pub struct KosarajuSCC {
+    marked: Vec<bool>,
+    id: Vec<usize>,
+    count: usize,
+}
+
+impl KosarajuSCC {
+    pub fn new(g: &Digraph) -> Self {
+        let mut this = KosarajuSCC {
+            marked: Vec::with_capacity(g.v()),
+            id: Vec::with_capacity(g.v()),
+            count: 0,
+        };
+
+        for _ in 0..g.v() {
+            this.marked.push(false);
+            this.id.push(0);
+        }
+
+        // 按反向图的逆后序进行深度优先搜索
+        let order = DepthFirstOrder::new(&g.reverse());
+
+        for v in order.reverse_post() {
+            if ! this.marked[v] {
+                this.dfs(g, v);
+                this.count += 1;
+            }
+        }
+
+        this
+    }
+
+    fn dfs(&mut self, g: &Digraph, v: usize) {
+        self.marked[v] = true;
+        self.id[v] = self.count;
+
+        for w in g.adj(v) {
+            if ! self.marked[*w] {
+                self.dfs(g, *w);
+            }
+        }
+    }
+
+    // 给定两个顶点，判断是否强连通
+    pub fn connected(&self, v: usize, w: usize) -> bool {
+        self.id[v] == self.id[w]
+    }
+
+    pub fn id(&self) -> &Vec<usize> {
+        &self.id
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    // 将每个强连通分量收缩为一个顶点得到的有向无环图
+    pub fn condensation(&self, g: &Digraph) -> Digraph {
+        condensation(g, &self.id, self.count)
+    }
+}
+
+#[test]
+fn test() {
+    let tiny_dg = [
+        (4, 2), (2, 3), (3, 2), (6, 0), (0, 1), (2, 0), (11, 12),
+        (12, 9), (9, 10), (9, 11), (7, 9), (10, 12), (11, 4), (4, 3),
+        (3, 5), (6, 8), (8, 6), (5, 4), (0, 5), (6, 4), (6, 9), (7, 6),
+    ];
+
+    let mut g = Digraph::with_capacity(13);
+
+    for &(v, w) in tiny_dg.iter() {
+        g.add_edge(v, w);
+    }
+
+    let scc = KosarajuSCC::new(&g);
+
+    assert_eq!(scc.count(), 5);
+    assert!(scc.connected(0, 5));
+    assert!(scc.connected(6, 8));
+    assert!(! scc.connected(1, 0));
+    assert!(! scc.connected(7, 6));
+
+    let mut components = Vec::new();
+
+    for _ in 0..scc.count() {
+        components.push(Vec::new());
+    }
+
+    for v in 0..g.v() {
+        components[scc.id()[v]].push(v);
+    }
+
+    assert_eq!(components[0], [1]);
+    assert_eq!(components[1], [0, 2, 3, 4, 5]);
+    assert_eq!(components[2], [9, 10, 11, 12]);
+    assert_eq!(components[3], [6, 8]);
+    assert_eq!(components[4], [7]);
+}
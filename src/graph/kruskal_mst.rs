@@ -1,18 +1,18 @@
 use std::rc::Rc;
 use std::collections::BinaryHeap;
 use super::union_find::UnionFind;
-use super::edge::Edge;
+use super::edge::{Edge, Weight};
 use super::edge_weighted_graph::EdgeWeightedGraph;
 
 
-pub struct KruskalMST {
-    mst: Vec<Rc<Edge>>,
-    pq: BinaryHeap<Rc<Edge>>,
+pub struct KruskalMST<W> {
+    mst: Vec<Rc<Edge<W>>>,
+    pq: BinaryHeap<Rc<Edge<W>>>,
     un: UnionFind,
 }
 
-impl KruskalMST {
-    pub fn new(g: &EdgeWeightedGraph) -> Self {
+impl<W: Weight> KruskalMST<W> {
+    pub fn new(g: &EdgeWeightedGraph<W>) -> Self {
         let mut this = KruskalMST {
             mst: Vec::new(),
             pq: BinaryHeap::new(),
@@ -39,7 +39,7 @@ impl KruskalMST {
         this
     }
 
-    pub fn edges(&self) -> Vec<Rc<Edge>> {
+    pub fn edges(&self) -> Vec<Rc<Edge<W>>> {
         let mut edges = Vec::new();
 
         for edge in &self.mst {
@@ -49,11 +49,11 @@ impl KruskalMST {
         edges
     }
 
-    pub fn weight(&self) -> f32 {
-        let mut weight = 0.0;
+    pub fn weight(&self) -> W {
+        let mut weight = W::zero();
 
         for edge in self.edges() {
-            weight += edge.weight();
+            weight = weight + edge.weight();
         }
 
         weight
@@ -70,7 +70,7 @@ fn test() {
         (6, 2, 0.40), (3, 6, 0.52), (6, 0, 0.58), (6, 4, 0.93),
     ];
 
-    let mut g = EdgeWeightedGraph::with_capacity(8);
+    let mut g: EdgeWeightedGraph<f32> = EdgeWeightedGraph::with_capacity(8);
 
     for &(v, w, weight) in tiny_ewg.iter() {
         g.add_edge(Edge::new(v, w, weight));
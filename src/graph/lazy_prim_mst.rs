@@ -1,17 +1,17 @@
 use std::rc::Rc;
 use std::collections::BinaryHeap;
-use super::edge::Edge;
+use super::edge::{Edge, Weight};
 use super::edge_weighted_graph::EdgeWeightedGraph;
 
 // 最小生成树 Prim 算法（延迟版本）
-pub struct LazyPrimMST {
-    marked: Vec<bool>,          // 最小生成树的顶点
-    mst: Vec<Rc<Edge>>,         // 最小生成树的边
-    pq: BinaryHeap<Rc<Edge>>,   // 横切边（包括失效的边）这个优先队列由小到大排序 原因看 Edge 的代码实现
+pub struct LazyPrimMST<W> {
+    marked: Vec<bool>,              // 最小生成树的顶点
+    mst: Vec<Rc<Edge<W>>>,          // 最小生成树的边
+    pq: BinaryHeap<Rc<Edge<W>>>,    // 横切边（包括失效的边）这个优先队列由小到大排序 原因看 Edge 的代码实现
 }
 
-impl LazyPrimMST {
-    pub fn new(g: &EdgeWeightedGraph) -> Self {
+impl<W: Weight> LazyPrimMST<W> {
+    pub fn new(g: &EdgeWeightedGraph<W>) -> Self {
         let mut this = LazyPrimMST {
             marked: Vec::with_capacity(g.v()),
             mst: Vec::with_capacity(g.v() - 1),
@@ -52,7 +52,7 @@ impl LazyPrimMST {
         this
     }
 
-    pub fn visit(&mut self, g: &EdgeWeightedGraph, v: usize) {
+    pub fn visit(&mut self, g: &EdgeWeightedGraph<W>, v: usize) {
         self.marked[v] = true;
 
         for edge in g.adj(v) {
@@ -65,16 +65,16 @@ impl LazyPrimMST {
     }
 
     // 最小生成树的边
-    pub fn edges(&self) -> Vec<Rc<Edge>> {
+    pub fn edges(&self) -> Vec<Rc<Edge<W>>> {
         self.mst.to_vec()
     }
 
     // 最小生成树的权重
-    pub fn weight(&self) -> f32 {
-        let mut weight = 0.0;
+    pub fn weight(&self) -> W {
+        let mut weight = W::zero();
 
         for edge in self.edges() {
-            weight += edge.weight();
+            weight = weight + edge.weight();
         }
 
         weight
@@ -90,7 +90,7 @@ fn test() {
         (6, 2, 0.40), (3, 6, 0.52), (6, 0, 0.58), (6, 4, 0.93),
     ];
 
-    let mut g = EdgeWeightedGraph::with_capacity(8);
+    let mut g: EdgeWeightedGraph<f32> = EdgeWeightedGraph::with_capacity(8);
 
     for &(v, w, weight) in tiny_ewg.iter() {
         g.add_edge(Edge::new(v, w, weight));
@@ -12,3 +12,16 @@ pub mod edge_weighted_graph;
 pub mod lazy_prim_mst;
 pub mod prim_mst;
 pub mod kruskal_mst;
+
+pub mod digraph;
+pub mod depth_first_order;
+pub mod directed_cycle;
+pub mod topological;
+pub mod condensation;
+pub mod kosaraju_scc;
+pub mod tarjan_scc;
+pub mod directed_edge;
+pub mod edge_weighted_digraph;
+pub mod dijkstra_sp;
+pub mod acyclic_sp;
+pub mod bellman_ford_sp;
\ No newline at end of file
@@ -1,43 +1,35 @@
-use std::f32;
 use std::rc::Rc;
 use std::cmp::Ordering;
-use super::edge::Edge;
+use super::edge::{Edge, Weight};
 use super::edge_weighted_graph::EdgeWeightedGraph;
 use super::super::queue::index_binary_heap::IndexBinaryHeap;
 
 // 实现最小索引优先队列，重写 Ord 和 PartialOrd
-#[derive(Eq, PartialEq)]
-struct Weight(u32);
+struct Key<W>(W);
 
-impl Weight {
-    pub fn new(n: f32) -> Self {
-        Weight(n.to_bits())
+impl<W: Weight> PartialEq for Key<W> {
+    fn eq(&self, other: &Key<W>) -> bool {
+        self.0.total_cmp(&other.0) == Ordering::Equal
     }
 }
 
-impl Ord for Weight {
-    fn cmp(&self, other: &Weight) -> Ordering {
-        other.0.cmp(&self.0)
-    }
-}
-
-impl PartialOrd for Weight {
-    fn partial_cmp(&self, other: &Weight) -> Option<Ordering> {
-        Some(self.cmp(other))
+impl<W: Weight> PartialOrd for Key<W> {
+    fn partial_cmp(&self, other: &Key<W>) -> Option<Ordering> {
+        Some(other.0.total_cmp(&self.0))
     }
 }
 
 
 // 最小生成树 Prim 算法（即时版本）
-pub struct PrimMST {
-    edge_to: Vec<Option<Rc<Edge>>>, // 路径
-    dist_to: Vec<f32>,              // 权重
-    marked: Vec<bool>,              // 顶点
-    pq: IndexBinaryHeap<Weight>,    // 最小索引优先队列
+pub struct PrimMST<W> {
+    edge_to: Vec<Option<Rc<Edge<W>>>>,  // 路径
+    dist_to: Vec<Option<W>>,            // 权重，None 表示不可达
+    marked: Vec<bool>,                  // 顶点
+    pq: IndexBinaryHeap<Key<W>>,        // 最小索引优先队列
 }
 
-impl PrimMST {
-    pub fn new(g: &EdgeWeightedGraph) -> Self {
+impl<W: Weight> PrimMST<W> {
+    pub fn new(g: &EdgeWeightedGraph<W>) -> Self {
         let mut this = PrimMST {
             edge_to: Vec::with_capacity(g.v()),
             dist_to: Vec::with_capacity(g.v()),
@@ -47,12 +39,12 @@ impl PrimMST {
 
         for _ in 0..g.v() {
             this.edge_to.push(None);
-            this.dist_to.push(f32::INFINITY);
+            this.dist_to.push(None);
             this.marked.push(false);
         }
 
-        this.dist_to[0] = 0.0;
-        this.pq.put(0, Weight::new(0.0));
+        this.dist_to[0] = Some(W::zero());
+        this.pq.put(0, Key(W::zero()));
 
         while ! this.pq.is_empty() {
             let v = this.pq.pop();
@@ -62,7 +54,7 @@ impl PrimMST {
         this
     }
 
-    pub fn visit(&mut self, g: &EdgeWeightedGraph, v: usize) {
+    pub fn visit(&mut self, g: &EdgeWeightedGraph<W>, v: usize) {
         self.marked[v] = true;
 
         for e in g.adj(v) {
@@ -72,16 +64,21 @@ impl PrimMST {
                 continue
             }
 
-            if e.weight() < self.dist_to[w] {
+            let closer = match self.dist_to[w] {
+                Some(dist) => e.weight().total_cmp(&dist) == Ordering::Less,
+                None => true,
+            };
+
+            if closer {
                 self.edge_to[w] = Some(e.clone());
-                self.dist_to[w] = e.weight();
+                self.dist_to[w] = Some(e.weight());
                 // 有则更新，无则添加
-                self.pq.put(w, Weight::new(e.weight()));
+                self.pq.put(w, Key(e.weight()));
             }
         }
     }
 
-    pub fn edges(&self) -> Vec<Rc<Edge>> {
+    pub fn edges(&self) -> Vec<Rc<Edge<W>>> {
         let mut edges = Vec::new();
 
         for e in &self.edge_to {
@@ -93,11 +90,11 @@ impl PrimMST {
         edges
     }
 
-    pub fn weight(&self) -> f32 {
-        let mut weight = 0.0;
+    pub fn weight(&self) -> W {
+        let mut weight = W::zero();
 
         for edge in self.edges() {
-            weight += edge.weight();
+            weight = weight + edge.weight();
         }
 
         weight
@@ -113,7 +110,7 @@ fn test() {
         (6, 2, 0.40), (3, 6, 0.52), (6, 0, 0.58), (6, 4, 0.93),
     ];
 
-    let mut g = EdgeWeightedGraph::with_capacity(8);
+    let mut g: EdgeWeightedGraph<f32> = EdgeWeightedGraph::with_capacity(8);
 
     for &(v, w, weight) in tiny_ewg.iter() {
         g.add_edge(Edge::new(v, w, weight));
@@ -130,4 +127,37 @@ fn test() {
     //    0-7 0.16
     assert_eq!(mst.edges().len(), g.v() - 1);
     assert_eq!(mst.weight(), 1.81);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_generic_weight() {
+    use super::lazy_prim_mst::LazyPrimMST;
+    use super::kruskal_mst::KruskalMST;
+
+    // 含负数权重的整数图
+    let edges = [
+        (0, 1, 4), (0, 2, -3), (1, 2, 2), (1, 3, 5),
+        (2, 3, -1), (3, 4, 7), (2, 4, 8), (1, 4, -6),
+    ];
+
+    let mut g = EdgeWeightedGraph::with_capacity(5);
+
+    for &(v, w, weight) in edges.iter() {
+        g.add_edge(Edge::new(v, w, weight as i64));
+    }
+
+    // -6 + -3 + -1 + 2
+    assert_eq!(PrimMST::new(&g).weight(), -8);
+    assert_eq!(LazyPrimMST::new(&g).weight(), -8);
+    assert_eq!(KruskalMST::new(&g).weight(), -8);
+
+    let mut g = EdgeWeightedGraph::with_capacity(5);
+
+    for &(v, w, weight) in edges.iter() {
+        g.add_edge(Edge::new(v, w, weight as f64 / 2.0));
+    }
+
+    assert_eq!(PrimMST::new(&g).weight(), -4.0);
+    assert_eq!(LazyPrimMST::new(&g).weight(), -4.0);
+    assert_eq!(KruskalMST::new(&g).weight(), -4.0);
+}
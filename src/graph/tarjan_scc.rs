@@ -0,0 +1,130 @@
+use super::digraph::Digraph;
+use super::condensation::condensation;
+
+// 强连通分量 Tarjan 算法
+pub struct TarjanSCC {
+    marked: Vec<bool>,
+    id: Vec<usize>,
+    low: Vec<usize>,        // low[v], v 通过子树能到达的最小前序编号
+    pre: usize,             // 前序编号计数
+    stack: Vec<usize>,
+    count: usize,
+}
+
+impl TarjanSCC {
+    pub fn new(g: &Digraph) -> Self {
+        let mut this = TarjanSCC {
+            marked: Vec::with_capacity(g.v()),
+            id: Vec::with_capacity(g.v()),
+            low: Vec::with_capacity(g.v()),
+            pre: 0,
+            stack: Vec::new(),
+            count: 0,
+        };
+
+        for _ in 0..g.v() {
+            this.marked.push(false);
+            this.id.push(0);
+            this.low.push(0);
+        }
+
+        for v in 0..g.v() {
+            if ! this.marked[v] {
+                this.dfs(g, v);
+            }
+        }
+
+        this
+    }
+
+    fn dfs(&mut self, g: &Digraph, v: usize) {
+        self.marked[v] = true;
+        self.low[v] = self.pre;
+        self.pre += 1;
+        self.stack.push(v);
+
+        let mut min = self.low[v];
+
+        for w in g.adj(v) {
+            if ! self.marked[*w] {
+                self.dfs(g, *w);
+            }
+
+            if self.low[*w] < min {
+                min = self.low[*w];
+            }
+        }
+
+        if min < self.low[v] {
+            self.low[v] = min;
+            return
+        }
+
+        // v 是强连通分量的根，将栈上 v 及其之后的顶点弹出
+        // 已归属分量的顶点 low 置为 g.v()，避免影响其他分量
+        while let Some(w) = self.stack.pop() {
+            self.id[w] = self.count;
+            self.low[w] = g.v();
+
+            if w == v {
+                break;
+            }
+        }
+
+        self.count += 1;
+    }
+
+    // 给定两个顶点，判断是否强连通
+    pub fn connected(&self, v: usize, w: usize) -> bool {
+        self.id[v] == self.id[w]
+    }
+
+    pub fn id(&self) -> &Vec<usize> {
+        &self.id
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    // 将每个强连通分量收缩为一个顶点得到的有向无环图
+    pub fn condensation(&self, g: &Digraph) -> Digraph {
+        condensation(g, &self.id, self.count)
+    }
+}
+
+#[test]
+fn test() {
+    use super::kosaraju_scc::KosarajuSCC;
+
+    let tiny_dg = [
+        (4, 2), (2, 3), (3, 2), (6, 0), (0, 1), (2, 0), (11, 12),
+        (12, 9), (9, 10), (9, 11), (7, 9), (10, 12), (11, 4), (4, 3),
+        (3, 5), (6, 8), (8, 6), (5, 4), (0, 5), (6, 4), (6, 9), (7, 6),
+    ];
+
+    let mut g = Digraph::with_capacity(13);
+
+    for &(v, w) in tiny_dg.iter() {
+        g.add_edge(v, w);
+    }
+
+    let scc = TarjanSCC::new(&g);
+
+    assert_eq!(scc.count(), 5);
+    assert!(scc.connected(0, 5));
+    assert!(scc.connected(6, 8));
+    assert!(! scc.connected(1, 0));
+    assert!(! scc.connected(7, 6));
+
+    // 与 Kosaraju 算法的结果相互校验
+    let kosaraju = KosarajuSCC::new(&g);
+
+    assert_eq!(scc.count(), kosaraju.count());
+
+    for v in 0..g.v() {
+        for w in 0..g.v() {
+            assert_eq!(scc.connected(v, w), kosaraju.connected(v, w));
+        }
+    }
+}
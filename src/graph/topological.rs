@@ -0,0 +1,140 @@
+use std::collections::VecDeque;
+use super::digraph::Digraph;
+use super::directed_cycle::DirectedCycle;
+use super::depth_first_order::DepthFirstOrder;
+
+// 拓扑排序
+pub struct Topological {
+    order: Option<Vec<usize>>,      // 拓扑序，有环时为 None
+    rank: Vec<Option<usize>>,       // rank[v], 顶点 v 在拓扑序中的位置
+    cycle: Option<Vec<usize>>,      // 有环时，其中的一个有向环
+}
+
+impl Topological {
+    // 基于深度优先搜索，有向无环图的逆后序即为拓扑序
+    pub fn new(g: &Digraph) -> Self {
+        let finder = DirectedCycle::new(g);
+
+        match finder.cycle() {
+            Some(cycle) => Topological::with_cycle(g, cycle.clone()),
+            None => Topological::with_order(g, DepthFirstOrder::new(g).reverse_post()),
+        }
+    }
+
+    // Kahn 算法，不断移除入度为 0 的顶点
+    pub fn kahn(g: &Digraph) -> Self {
+        let mut indegree = Vec::with_capacity(g.v());
+        let mut queue = VecDeque::new();
+        let mut order = Vec::with_capacity(g.v());
+
+        for v in 0..g.v() {
+            indegree.push(g.in_degree(v));
+
+            if g.in_degree(v) == 0 {
+                queue.push_back(v);
+            }
+        }
+
+        while let Some(v) = queue.pop_front() {
+            order.push(v);
+
+            for w in g.adj(v) {
+                indegree[*w] -= 1;
+
+                if indegree[*w] == 0 {
+                    queue.push_back(*w);
+                }
+            }
+        }
+
+        // 还有顶点未被移除，说明存在环
+        if order.len() < g.v() {
+            let finder = DirectedCycle::new(g);
+            Topological::with_cycle(g, finder.cycle().unwrap().clone())
+        }
+        else {
+            Topological::with_order(g, order)
+        }
+    }
+
+    fn with_order(g: &Digraph, order: Vec<usize>) -> Self {
+        let mut rank = vec![None; g.v()];
+
+        for (i, v) in order.iter().enumerate() {
+            rank[*v] = Some(i);
+        }
+
+        Topological { order: Some(order), rank, cycle: None }
+    }
+
+    fn with_cycle(g: &Digraph, cycle: Vec<usize>) -> Self {
+        Topological { order: None, rank: vec![None; g.v()], cycle: Some(cycle) }
+    }
+
+    // 是否为有向无环图
+    pub fn is_dag(&self) -> bool {
+        self.order.is_some()
+    }
+
+    // 拓扑序
+    pub fn order(&self) -> Option<&Vec<usize>> {
+        self.order.as_ref()
+    }
+
+    // 顶点在拓扑序中的位置
+    pub fn rank(&self, v: usize) -> Option<usize> {
+        self.rank[v]
+    }
+
+    // 不是有向无环图时，导致失败的有向环
+    pub fn cycle(&self) -> Option<&Vec<usize>> {
+        self.cycle.as_ref()
+    }
+}
+
+#[test]
+fn test() {
+    let tiny_dag = [
+        (2, 3), (0, 6), (0, 1), (2, 0), (11, 12), (9, 12), (9, 10), (9, 11),
+        (3, 5), (8, 7), (5, 4), (0, 5), (6, 4), (6, 9), (7, 6),
+    ];
+
+    let mut g = Digraph::with_capacity(13);
+
+    for &(v, w) in tiny_dag.iter() {
+        g.add_edge(v, w);
+    }
+
+    let dfs = Topological::new(&g);
+    let kahn = Topological::kahn(&g);
+
+    assert!(dfs.is_dag());
+    assert!(kahn.is_dag());
+    assert_eq!(dfs.cycle(), None);
+    assert_eq!(dfs.order(), Some(&vec![8, 7, 2, 3, 0, 5, 1, 6, 9, 11, 10, 12, 4]));
+    assert_eq!(kahn.order(), Some(&vec![2, 8, 3, 0, 7, 1, 5, 6, 4, 9, 10, 11, 12]));
+
+    // 每条边 v -> w 中，v 都排在 w 之前
+    for t in [dfs, kahn].iter() {
+        assert_eq!(t.order().unwrap().len(), g.v());
+
+        for v in 0..g.v() {
+            for w in g.adj(v) {
+                assert!(t.rank(v).unwrap() < t.rank(*w).unwrap());
+            }
+        }
+    }
+
+    // 有环
+    g.add_edge(4, 2);
+
+    let dfs = Topological::new(&g);
+    let kahn = Topological::kahn(&g);
+
+    assert!(! dfs.is_dag());
+    assert!(! kahn.is_dag());
+    assert_eq!(dfs.order(), None);
+    assert_eq!(dfs.rank(0), None);
+    assert_eq!(dfs.cycle(), Some(&vec![4, 2, 3, 5, 4]));
+    assert_eq!(kahn.cycle(), dfs.cycle());
+}
@@ -3,4 +3,5 @@ pub mod sort;
 pub mod queue;
 pub mod tree;
 pub mod graph;
-pub mod string;
\ No newline at end of file
+pub mod string;
+pub mod compression;
\ No newline at end of file
@@ -1,3 +1,5 @@
+use super::sorter::Sorter;
+
 pub fn bubble_sort<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], compare: &F) {
     let mut len = seq.len();
 
@@ -11,6 +13,19 @@ pub fn bubble_sort<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], compare: &F) {
     }
 }
 
+// 冒泡排序，只交换相邻的逆序元素，是稳定的
+pub struct BubbleSort;
+
+impl Sorter for BubbleSort {
+    fn is_stable(&self) -> bool {
+        true
+    }
+
+    fn sort_with<T, F: Fn(&T, &T) -> bool>(&self, seq: &mut [T], less: &F) {
+        bubble_sort(seq, less);
+    }
+}
+
 #[test]
 fn test() {
     // 从大到小排序数字
@@ -0,0 +1,144 @@
+use super::insert_sort::insert_sort;
+use super::merge_sort::merge_sort;
+use super::permute::permute;
+
+// 桶排序，适用于元素是均匀分布的浮点数的情况
+// 按值在 [min, max] 中的位置把元素分到 n 个桶中，每个桶平均只有一个元素，桶内用插入排序
+// 值均匀分布时期望的时间与元素数量成正比，是稳定的
+// compare 与值的大小不一致时分桶的结果不是有序的，这时改用归并排序
+// 值必须是有限的数，NaN 或无穷大时 panic
+pub fn bucket_sort<T: Copy + Into<f64>, F: Fn(&T, &T) -> bool>(seq: &mut [T], compare: &F) {
+    let keys: Vec<f64> = seq.iter().map(|&x| x.into()).collect();
+    sort(seq, &keys, compare);
+}
+
+// 按 key 返回的浮点数排序，键相同的元素保持原来的顺序
+pub fn bucket_sort_by_key<T, F: Fn(&T) -> f64>(seq: &mut [T], key: &F) {
+    let keys: Vec<f64> = seq.iter().map(key).collect();
+    sort(seq, &keys, &|a: &T, b: &T| key(a) < key(b));
+}
+
+fn sort<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], keys: &[f64], compare: &F) {
+    let len = seq.len();
+    assert!(keys.iter().all(|k| k.is_finite()), "bucket_sort keys must be finite");
+
+    if len <= 1 {
+        return
+    }
+
+    let mut min = 0;
+    let mut max = 0;
+
+    for (i, &k) in keys.iter().enumerate() {
+        if k < keys[min] {
+            min = i;
+        }
+
+        if k > keys[max] {
+            max = i;
+        }
+    }
+
+    // 先除以 2 再相减，min 和 max 接近 -f64::MAX 和 f64::MAX 时也不会溢出为无穷大
+    let low = keys[min] / 2.0;
+    let width = keys[max] / 2.0 - low;
+
+    let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); len];
+
+    for (i, &k) in keys.iter().enumerate() {
+        let b = if width > 0.0 {
+            (((k / 2.0 - low) / width * len as f64) as usize).min(len - 1)
+        }
+        else {
+            0
+        };
+
+        buckets[b].push(i);
+    }
+
+    // 从大到小排序时倒过来取桶
+    if compare(&seq[max], &seq[min]) {
+        buckets.reverse();
+    }
+
+    let mut order = Vec::with_capacity(len);
+
+    for bucket in buckets.iter_mut() {
+        insert_sort(bucket, &|&i, &j| compare(&seq[i], &seq[j]));
+        order.extend_from_slice(bucket);
+    }
+
+    // 相邻的元素逆序，或者相等但顺序颠倒，说明 compare 与值的大小不一致
+    let sorted = order.windows(2).all(|w| {
+        let (i, j) = (w[0], w[1]);
+        ! compare(&seq[j], &seq[i]) && (i < j || compare(&seq[i], &seq[j]))
+    });
+
+    if sorted {
+        permute(seq, &mut order);
+    }
+    else {
+        merge_sort(seq, compare);
+    }
+}
+
+#[test]
+fn test() {
+    extern crate rand;
+
+    let mut seq = [0.78, 0.17, 0.39, 0.26, 0.72, 0.94, 0.21, 0.12, 0.23, 0.68];
+    bucket_sort(&mut seq, &|x, y| x < y);
+    assert_eq!(seq, [0.12, 0.17, 0.21, 0.23, 0.26, 0.39, 0.68, 0.72, 0.78, 0.94]);
+
+    // 从大到小，负数和相同的键
+    let mut seq = [(-1.5, 'a'), (3.0, 'b'), (-1.5, 'c'), (0.0, 'd'), (3.0, 'e')];
+    bucket_sort_by_key(&mut seq, &|&(x, _)| -x);
+    assert_eq!(seq, [(3.0, 'b'), (3.0, 'e'), (0.0, 'd'), (-1.5, 'a'), (-1.5, 'c')]);
+
+    // 所有键都相同
+    let mut seq = ["b", "a", "c"];
+    bucket_sort_by_key(&mut seq, &|_| 1.0);
+    assert_eq!(seq, ["b", "a", "c"]);
+
+    // 从大到小，以及 f64 之外可以转换为 f64 的类型
+    let mut seq = [3_i32, -7, 12, 0, 5, -7];
+    bucket_sort(&mut seq, &|x, y| x > y);
+    assert_eq!(seq, [12, 5, 3, 0, -7, -7]);
+
+    let mut seq = [0.5_f32, 0.25, 0.75];
+    bucket_sort(&mut seq, &|x, y| x < y);
+    assert_eq!(seq, [0.25, 0.5, 0.75]);
+
+    // 最大值和最小值相差超过 f64::MAX
+    let mut seq = [f64::MAX, 1.0, -f64::MAX, 0.0, f64::MAX / 3.0, -1.0];
+    bucket_sort(&mut seq, &|x, y| x < y);
+    assert_eq!(seq, [-f64::MAX, -1.0, 0.0, 1.0, f64::MAX / 3.0, f64::MAX]);
+
+    // 只比较绝对值，分桶的结果不是有序的，改用归并排序，仍然是稳定的
+    let mut seq = [3, -1, -3, 2, 1];
+    bucket_sort(&mut seq, &|x: &i32, y: &i32| x.abs() < y.abs());
+    assert_eq!(seq, [-1, 1, 2, 3, -3]);
+
+    let mut seq: [f64; 0] = [];
+    bucket_sort_by_key(&mut seq, &|&x| x);
+
+    let mut seq: Vec<f64> = (0..2000).map(|_| rand::random::<f64>() * 100.0).collect();
+    let mut expected = seq.clone();
+    expected.sort_by(|x, y| x.partial_cmp(y).unwrap());
+    bucket_sort(&mut seq, &|x, y| x < y);
+    assert_eq!(seq, expected);
+}
+
+#[test]
+#[should_panic(expected = "keys must be finite")]
+fn test_nan() {
+    let mut seq = [0.5, f64::NAN, 0.1];
+    bucket_sort_by_key(&mut seq, &|&x| x);
+}
+
+#[test]
+#[should_panic(expected = "keys must be finite")]
+fn test_infinite() {
+    let mut seq = [0.5, f64::INFINITY, 0.1];
+    bucket_sort_by_key(&mut seq, &|&x| x);
+}
@@ -0,0 +1,183 @@
+use super::merge_sort::merge_sort;
+use super::permute::permute;
+
+// 键的范围超过 max(4n, 2^16) 时计数数组太大，改用归并排序
+const MIN_RANGE: usize = 1 << 16;
+
+// 可以用作计数排序的键的有界整数
+pub trait CountingKey {
+    fn key(&self) -> i128;
+}
+
+macro_rules! counting_key {
+    ($($t:ty)*) => ($(
+        impl CountingKey for $t {
+            fn key(&self) -> i128 {
+                *self as i128
+            }
+        }
+    )*)
+}
+
+counting_key! { i8 i16 i32 i64 isize u8 u16 u32 u64 usize char bool }
+
+// 计数排序（键索引计数法），元素本身是有界的整数
+// 与其他排序的调用方式相同，compare 只用来决定不同的值之间的顺序，相等的值一定排在一起
+// 时间和空间都与元素数量加上键的范围成正比，是稳定的
+pub fn counting_sort<T: CountingKey, F: Fn(&T, &T) -> bool>(seq: &mut [T], compare: &F) {
+    let keys: Vec<i128> = seq.iter().map(|x| x.key()).collect();
+    let min = keys.iter().cloned().min().unwrap_or(0);
+    let max = keys.iter().cloned().max().unwrap_or(0);
+
+    match range(seq.len(), (max - min) as u128) {
+        Some(r) => {
+            let offsets: Vec<usize> = keys.iter().map(|&k| (k - min) as usize).collect();
+            sort(seq, &offsets, r, compare);
+        },
+        None => merge_sort(seq, compare),
+    }
+}
+
+// 按 key 返回的非负整数排序，键相同的元素保持原来的顺序
+pub fn counting_sort_by_key<T, F: Fn(&T) -> usize>(seq: &mut [T], key: &F) {
+    let keys: Vec<usize> = seq.iter().map(key).collect();
+    let min = keys.iter().cloned().min().unwrap_or(0);
+    let max = keys.iter().cloned().max().unwrap_or(0);
+    let less = |a: &T, b: &T| key(a) < key(b);
+
+    match range(seq.len(), (max - min) as u128) {
+        Some(r) => {
+            let offsets: Vec<usize> = keys.iter().map(|&k| k - min).collect();
+            sort(seq, &offsets, r, &less);
+        },
+        None => merge_sort(seq, &less),
+    }
+}
+
+// 键的范围为 span + 1，过大时返回 None
+fn range(len: usize, span: u128) -> Option<usize> {
+    let limit = len.saturating_mul(4).max(MIN_RANGE);
+
+    if span < limit as u128 {
+        Some(span as usize + 1)
+    }
+    else {
+        None
+    }
+}
+
+// 元素 i 的键为 offsets[i]，小于 r
+// 先用 compare 排序出现过的键，相邻的等价的键合并为一类，再按类做键索引计数
+fn sort<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], offsets: &[usize], r: usize, compare: &F) {
+    let len = seq.len();
+    let mut count = vec![0; r];
+    // 每个键第一次出现的位置，作为比较时的代表
+    let mut first = vec![0; r];
+
+    for (i, &o) in offsets.iter().enumerate() {
+        if count[o] == 0 {
+            first[o] = i;
+        }
+
+        count[o] += 1;
+    }
+
+    let mut distinct: Vec<usize> = (0..r).filter(|&o| count[o] > 0).collect();
+    merge_sort(&mut distinct, &|&a, &b| compare(&seq[first[a]], &seq[first[b]]));
+
+    // 复用 count 保存每个键所属的类
+    let mut classes = 0;
+
+    for k in 0..distinct.len() {
+        if k > 0 && compare(&seq[first[distinct[k - 1]]], &seq[first[distinct[k]]]) {
+            classes += 1;
+        }
+
+        count[distinct[k]] = classes;
+    }
+
+    // 计算出现频率，再将频率转换为索引
+    let mut start = vec![0; classes + 2];
+
+    for &o in offsets.iter() {
+        start[count[o] + 1] += 1;
+    }
+
+    for c in 0..classes + 1 {
+        start[c + 1] += start[c];
+    }
+
+    // 将元素分类，order[i] 为排序后第 i 个元素原来的位置
+    let mut order = vec![0; len];
+
+    for (i, &o) in offsets.iter().enumerate() {
+        let c = count[o];
+        order[start[c]] = i;
+        start[c] += 1;
+    }
+
+    permute(seq, &mut order);
+}
+
+#[test]
+fn test() {
+    extern crate rand;
+
+    // 从大到小排序数字
+    let mut seq = [4, 65, 2, -31, 0, 99, 2, 83, 782, 1];
+    counting_sort(&mut seq, &|x, y| x > y);
+    assert_eq!(seq, [782, 99, 83, 65, 4, 2, 2, 1, 0, -31]);
+
+    // 按字母顺序排序字符
+    let mut seq = ['h', 'o', 't', 'e', 'l', 'a', 'r', 't'];
+    counting_sort(&mut seq, &|x, y| x < y);
+    assert_eq!(seq, ['a', 'e', 'h', 'l', 'o', 'r', 't', 't']);
+
+    // 按长度排序字符串，长度相同的保持原来的顺序
+    let mut seq = ["airplane", "art", "beach", "car", "hotel", "house"];
+    counting_sort_by_key(&mut seq, &|x| x.len());
+    assert_eq!(seq, ["art", "car", "beach", "hotel", "house", "airplane"]);
+
+    // 按组号分组学生
+    let mut seq: Vec<(String, usize)> = vec![
+        ("Anderson".to_string(), 2), ("Brown".to_string(), 3), ("Davis".to_string(), 3),
+        ("Garcia".to_string(), 4), ("Harris".to_string(), 1), ("Jackson".to_string(), 3),
+    ];
+    counting_sort_by_key(&mut seq, &|&(_, group)| group);
+    let names: Vec<&str> = seq.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["Harris", "Anderson", "Brown", "Davis", "Jackson", "Garcia"]);
+
+    // 只比较奇偶性，奇偶性相同的保持原来的顺序
+    let mut seq = [5, 2, 7, 4, 1, 2, 8];
+    counting_sort(&mut seq, &|x, y| x % 2 < y % 2);
+    assert_eq!(seq, [2, 4, 2, 8, 5, 7, 1]);
+
+    // 键很大但范围很小，以及范围很大时改用归并排序
+    let mut seq = [usize::MAX, usize::MAX - 2, usize::MAX - 1, usize::MAX];
+    counting_sort(&mut seq, &|x, y| x < y);
+    assert_eq!(seq, [usize::MAX - 2, usize::MAX - 1, usize::MAX, usize::MAX]);
+
+    let mut seq = [usize::MAX, 0, 5, usize::MAX, 0];
+    counting_sort_by_key(&mut seq, &|&x| x);
+    assert_eq!(seq, [0, 0, 5, usize::MAX, usize::MAX]);
+
+    let mut seq = [i64::MAX, i64::MIN, 0];
+    counting_sort(&mut seq, &|x, y| x < y);
+    assert_eq!(seq, [i64::MIN, 0, i64::MAX]);
+
+    let mut seq: [usize; 0] = [];
+    counting_sort(&mut seq, &|x, y| x < y);
+    counting_sort_by_key(&mut seq, &|&x| x);
+
+    let mut seq: Vec<(usize, usize)> = (0..1000).map(|i| (rand::random::<usize>() % 50, i)).collect();
+    let mut expected = seq.clone();
+    expected.sort_by_key(|&(k, _)| k);
+    counting_sort_by_key(&mut seq, &|&(k, _)| k);
+    assert_eq!(seq, expected);
+
+    let mut seq: Vec<i16> = (0..1000).map(|_| rand::random::<i16>()).collect();
+    let mut expected = seq.clone();
+    expected.sort();
+    counting_sort(&mut seq, &|x, y| x < y);
+    assert_eq!(seq, expected);
+}
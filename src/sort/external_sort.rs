@@ -0,0 +1,426 @@
+// 外部归并排序，用于无法全部放进内存的数据
+// 从输入中读取记录，估算的内存用量达到预算时对这一段排序，写入一个临时文件，称为一个顺串
+// 最后用一个以顺串编号为次要键的最小堆做多路归并，结果是稳定的
+// 同时归并的顺串数量受内存预算限制，顺串太多时分多遍归并，每遍把相邻的若干顺串归并为一个
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use super::merge_sort::merge_sort;
+use super::heap_sort::sift_down;
+
+// 记录的序列化方式
+pub trait Codec {
+    type Record;
+
+    // 读取下一条记录，输入结束时返回 None
+    fn read<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<Self::Record>>;
+
+    fn write<W: Write>(&self, record: &Self::Record, writer: &mut W) -> io::Result<()>;
+
+    // 记录在内存中占用的字节数的估计，必须包括记录本身和它拥有的堆内存
+    // 默认只计算 size_of_val，记录拥有堆内存时（比如 String、Vec）需要重写，否则会超出内存预算
+    fn size(&self, record: &Self::Record) -> usize {
+        mem::size_of_val(record)
+    }
+}
+
+// 以换行符分隔的文本行，记录不包括换行符
+pub struct LineCodec;
+
+impl Codec for LineCodec {
+    type Record = String;
+
+    fn read<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<String>> {
+        let mut line = String::new();
+
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None)
+        }
+
+        if line.ends_with('\n') {
+            line.pop();
+        }
+
+        Ok(Some(line))
+    }
+
+    fn write<W: Write>(&self, record: &String, writer: &mut W) -> io::Result<()> {
+        writer.write_all(record.as_bytes())?;
+        writer.write_all(b"\n")
+    }
+
+    fn size(&self, record: &String) -> usize {
+        mem::size_of::<String>() + record.capacity()
+    }
+}
+
+// 归并时每个读写缓冲区至少的字节数
+const MIN_BUFFER: usize = 4096;
+
+// 用于生成不重复的临时文件名
+static RUN_ID: AtomicUsize = AtomicUsize::new(0);
+
+// 写入临时文件的顺串，离开作用域时删除文件
+struct Run {
+    path: PathBuf,
+}
+
+impl Run {
+    // 只创建新文件，同名的文件已存在时（比如崩溃的进程留下的）换下一个编号，不覆盖也不会误删
+    fn create(dir: &Path) -> io::Result<(Self, File)> {
+        loop {
+            let id = RUN_ID.fetch_add(1, Ordering::Relaxed);
+            let path = dir.join(format!("external_sort.{}.{}.run", process::id(), id));
+
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(file) => return Ok((Run { path }, file)),
+                Err(ref e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl Drop for Run {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+pub struct ExternalSorter<C> {
+    codec: C,
+    // 内存预算：每个顺串估算的内存用量上限（至少包含一条记录），归并时也由它决定缓冲区大小和同时打开的顺串数量
+    memory: usize,
+    temp_dir: PathBuf,
+}
+
+impl<C: Codec> ExternalSorter<C> {
+    // 临时文件默认放在系统的临时目录中
+    pub fn new(codec: C, memory: usize) -> Self {
+        ExternalSorter { codec, memory, temp_dir: env::temp_dir() }
+    }
+
+    pub fn temp_dir<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.temp_dir = dir.into();
+        self
+    }
+
+    // 一次最多同时归并的顺串数量，加上输出一共 fan_in + 1 个缓冲区，至少为 2
+    fn fan_in(&self) -> usize {
+        (self.memory / MIN_BUFFER).saturating_sub(1).max(2)
+    }
+
+    // 从内存预算中平分给每个读写缓冲区的字节数
+    fn buffer(&self) -> usize {
+        (self.memory / (self.fan_in() + 1)).max(1)
+    }
+
+    // 把排好序的记录写入一个新的顺串
+    fn spill(&self, records: &[C::Record]) -> io::Result<Run> {
+        let (run, file) = Run::create(&self.temp_dir)?;
+        let mut writer = BufWriter::with_capacity(self.buffer(), file);
+
+        for record in records {
+            self.codec.write(record, &mut writer)?;
+        }
+
+        writer.flush()?;
+        Ok(run)
+    }
+
+    // 读取 input 中的所有记录，内存预算用完时排序并写出一个顺串
+    // 返回已写出的顺串、排好序的剩余记录和记录的总数
+    // 每条记录的开销是它自身的估计，加上 Vec 中的一个位置和 merge_sort 的 order、aux 中的两个下标
+    // Vec 按剩余的预算扩容，空闲的位置也计入用量
+    fn split<R, F>(&self, input: &mut R, compare: &F) -> io::Result<(Vec<Run>, Vec<C::Record>, usize)>
+        where R: BufRead, F: Fn(&C::Record, &C::Record) -> bool
+    {
+        let slot = mem::size_of::<C::Record>();
+        let per = slot + 2 * mem::size_of::<usize>();
+        let mut runs = Vec::new();
+        let mut records = Vec::new();
+        // 记录拥有的、不在 Vec 中的字节数
+        let mut heap = 0;
+        let mut count = 0;
+
+        while let Some(record) = self.codec.read(input)? {
+            if records.len() == records.capacity() {
+                let mut room = self.memory.saturating_sub(heap + records.capacity() * per) / per;
+
+                // 剩余的预算放不下一条记录
+                if room == 0 && ! records.is_empty() {
+                    merge_sort(&mut records, compare);
+                    runs.push(self.spill(&records)?);
+                    records = Vec::new();
+                    heap = 0;
+                    room = self.memory / per;
+                }
+
+                // 最多翻倍，且不超过剩余的预算，但至少能放下这一条记录
+                records.reserve_exact(records.len().max(16).min(room).max(1));
+            }
+
+            heap += self.codec.size(&record).saturating_sub(slot);
+            records.push(record);
+            count += 1;
+
+            if heap + records.capacity() * per >= self.memory {
+                merge_sort(&mut records, compare);
+                runs.push(self.spill(&records)?);
+                records = Vec::new();
+                heap = 0;
+            }
+        }
+
+        merge_sort(&mut records, compare);
+        Ok((runs, records, count))
+    }
+
+    // 排序 input 中的所有记录，写入 output，返回记录的数量
+    pub fn sort<R, W, F>(&self, mut input: R, mut output: W, compare: &F) -> io::Result<usize>
+        where R: BufRead, W: Write, F: Fn(&C::Record, &C::Record) -> bool
+    {
+        let (mut runs, records, count) = self.split(&mut input, compare)?;
+
+        // 全部记录都在内存中，不需要临时文件
+        if runs.is_empty() {
+            for record in records.iter() {
+                self.codec.write(record, &mut output)?;
+            }
+
+            output.flush()?;
+            return Ok(count)
+        }
+
+        if ! records.is_empty() {
+            runs.push(self.spill(&records)?);
+        }
+
+        drop(records);
+
+        // 每遍按顺序把相邻的 fan_in 个顺串归并为一个，保持顺串的先后顺序，结果依然稳定
+        // 归并完的顺串立即删除
+        let fan_in = self.fan_in();
+
+        while runs.len() > fan_in {
+            let mut rest = runs.into_iter();
+            runs = Vec::new();
+
+            loop {
+                let group: Vec<Run> = rest.by_ref().take(fan_in).collect();
+
+                if group.is_empty() {
+                    break
+                }
+
+                let (run, file) = Run::create(&self.temp_dir)?;
+                let mut writer = BufWriter::with_capacity(self.buffer(), file);
+                self.merge(&group, &mut writer, compare)?;
+                runs.push(run);
+            }
+        }
+
+        self.merge(&runs, &mut output, compare)?;
+        Ok(count)
+    }
+
+    // 多路归并，堆中保存每个顺串当前最小的记录和顺串的编号，runs 不超过 fan_in 个
+    fn merge<W, F>(&self, runs: &[Run], output: &mut W, compare: &F) -> io::Result<()>
+        where W: Write, F: Fn(&C::Record, &C::Record) -> bool
+    {
+        let mut readers = Vec::with_capacity(runs.len());
+        let mut heap = Vec::with_capacity(runs.len());
+
+        for (i, run) in runs.iter().enumerate() {
+            let mut reader = BufReader::with_capacity(self.buffer(), File::open(&run.path)?);
+
+            if let Some(record) = self.codec.read(&mut reader)? {
+                heap.push((record, i));
+            }
+
+            readers.push(reader);
+        }
+
+        // sift_down 维护的是最大堆，反转比较得到最小堆，记录相等时编号小的顺串在前
+        let greater = |a: &(C::Record, usize), b: &(C::Record, usize)| {
+            compare(&b.0, &a.0) || (! compare(&a.0, &b.0) && b.1 < a.1)
+        };
+
+        let len = heap.len();
+
+        for start in (0..len / 2).rev() {
+            sift_down(&mut heap, start, len - 1, &greater);
+        }
+
+        while ! heap.is_empty() {
+            let i = heap[0].1;
+            self.codec.write(&heap[0].0, output)?;
+
+            match self.codec.read(&mut readers[i])? {
+                Some(record) => heap[0].0 = record,
+                None => {
+                    heap.swap_remove(0);
+                },
+            }
+
+            if heap.len() > 1 {
+                let finish = heap.len() - 1;
+                sift_down(&mut heap, 0, finish, &greater);
+            }
+        }
+
+        output.flush()
+    }
+}
+
+#[test]
+fn test() {
+    extern crate rand;
+
+    use std::io::Cursor;
+
+    let input = "she\nsells\nseashells\nby\nthe\nsea\nshore\n";
+    let mut output = Vec::new();
+    let sorter = ExternalSorter::new(LineCodec, 64);
+    assert_eq!(sorter.sort(Cursor::new(input), &mut output, &|x, y| x < y).unwrap(), 7);
+    assert_eq!(String::from_utf8(output).unwrap(), "by\nsea\nseashells\nsells\nshe\nshore\nthe\n");
+
+    // 没有结尾的换行符，内存足够时不产生临时文件
+    let mut output = Vec::new();
+    let sorter = ExternalSorter::new(LineCodec, 1 << 20);
+    sorter.sort(Cursor::new("b\nc\na"), &mut output, &|x, y| x > y).unwrap();
+    assert_eq!(output, b"c\nb\na\n");
+
+    let mut output = Vec::new();
+    assert_eq!(sorter.sort(Cursor::new(""), &mut output, &|x, y| x < y).unwrap(), 0);
+    assert!(output.is_empty());
+
+    // 自定义的序列化方式，(键, 序号) 各占 4 个字节
+    struct PairCodec;
+
+    impl Codec for PairCodec {
+        type Record = (u32, u32);
+
+        fn read<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<(u32, u32)>> {
+            let mut buf = [0; 8];
+
+            match reader.read_exact(&mut buf) {
+                Ok(()) => {},
+                Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+                Err(e) => return Err(e),
+            }
+
+            let key = u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]);
+            let seq = u32::from_le_bytes([buf[4], buf[5], buf[6], buf[7]]);
+            Ok(Some((key, seq)))
+        }
+
+        fn write<W: Write>(&self, record: &(u32, u32), writer: &mut W) -> io::Result<()> {
+            writer.write_all(&record.0.to_le_bytes())?;
+            writer.write_all(&record.1.to_le_bytes())
+        }
+    }
+
+    // 每条记录 8 个字节加上两个下标，每个顺串 33 条记录，只按键排序，检查稳定性
+    let records: Vec<(u32, u32)> = (0..5000).map(|i| (rand::random::<u32>() % 100, i)).collect();
+    let mut input = Vec::new();
+
+    for record in records.iter() {
+        PairCodec.write(record, &mut input).unwrap();
+    }
+
+    let sorter = ExternalSorter::new(PairCodec, 800);
+    let (runs, rest, count) = sorter.split(&mut Cursor::new(&input), &|x, y| x.0 < y.0).unwrap();
+    assert_eq!((runs.len(), rest.len(), count), (151, 17, 5000));
+
+    for run in runs.iter() {
+        assert_eq!(fs::metadata(&run.path).unwrap().len(), 33 * 8);
+    }
+
+    drop(runs);
+
+    let mut output = Vec::new();
+    sorter.sort(Cursor::new(input), &mut output, &|x, y| x.0 < y.0).unwrap();
+
+    let mut reader = Cursor::new(output);
+    let mut actual = Vec::new();
+
+    while let Some(record) = PairCodec.read(&mut reader).unwrap() {
+        actual.push(record);
+    }
+
+    let mut expected = records.clone();
+    expected.sort_by_key(|&(key, _)| key);
+    assert_eq!(actual, expected);
+
+    // 顺串的数量超过 fan_in 时分多遍归并：61 个顺串，每遍最多归并 8 个
+    let sorter = ExternalSorter::new(PairCodec, 40_000);
+    assert_eq!(sorter.fan_in(), 8);
+    assert_eq!(sorter.buffer(), 4444);
+    assert_eq!(ExternalSorter::new(PairCodec, 100).fan_in(), 2);
+
+    let records: Vec<(u32, u32)> = (0..100_000).map(|i| (rand::random::<u32>() % 1000, i)).collect();
+    let mut input = Vec::new();
+
+    for record in records.iter() {
+        PairCodec.write(record, &mut input).unwrap();
+    }
+
+    let mut output = Vec::new();
+    sorter.sort(Cursor::new(input), &mut output, &|x, y| x.0 < y.0).unwrap();
+
+    let mut reader = Cursor::new(output);
+    let mut actual = Vec::new();
+
+    while let Some(record) = PairCodec.read(&mut reader).unwrap() {
+        actual.push(record);
+    }
+
+    let mut expected = records.clone();
+    expected.sort_by_key(|&(key, _)| key);
+    assert_eq!(actual, expected);
+
+    // 很小的内存预算，几百个顺串，每遍只归并 2 个
+    let lines: Vec<String> = (0..3000).map(|_| format!("{}", rand::random::<u32>() % 500)).collect();
+    let mut output = Vec::new();
+    let sorter = ExternalSorter::new(LineCodec, 100);
+    sorter.sort(Cursor::new(lines.join("\n")), &mut output, &|x, y| x < y).unwrap();
+
+    let mut expected = lines.clone();
+    expected.sort();
+    assert_eq!(String::from_utf8(output).unwrap().lines().collect::<Vec<_>>(), expected);
+
+    // 临时文件都已删除，已存在的同名文件不会被覆盖或删除
+    let dir = env::temp_dir().join(format!("external_sort_test.{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let next = RUN_ID.load(Ordering::Relaxed);
+    let planted: Vec<PathBuf> = (next..next + 3)
+        .map(|id| dir.join(format!("external_sort.{}.{}.run", process::id(), id)))
+        .collect();
+
+    for path in planted.iter() {
+        fs::write(path, "keep").unwrap();
+    }
+
+    let lines: Vec<String> = (0..1000).map(|_| format!("{}", rand::random::<u16>())).collect();
+    let mut output = Vec::new();
+    let sorter = ExternalSorter::new(LineCodec, 1000).temp_dir(&dir);
+    sorter.sort(Cursor::new(lines.join("\n")), &mut output, &|x, y| x < y).unwrap();
+
+    let mut expected = lines.clone();
+    expected.sort();
+    assert_eq!(String::from_utf8(output).unwrap().lines().collect::<Vec<_>>(), expected);
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), planted.len());
+
+    for path in planted.iter() {
+        assert_eq!(fs::read_to_string(path).unwrap(), "keep");
+        fs::remove_file(path).unwrap();
+    }
+
+    fs::remove_dir(&dir).unwrap();
+}
@@ -1,3 +1,5 @@
+use super::sorter::Sorter;
+
 pub fn heap_sort<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], compare: &F) {
     let len = seq.len();
 
@@ -13,7 +15,8 @@ pub fn heap_sort<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], compare: &F) {
     }
 }
 
-fn sift_down<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], mut root: usize, finish: usize, compare: &F) {
+// 以 root 为根下沉，堆的范围为 seq[..=finish]，compare 意义下较大的元素在上
+pub(crate) fn sift_down<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], mut root: usize, finish: usize, compare: &F) {
     let mut child;
 
     while root < finish {
@@ -38,6 +41,19 @@ fn sift_down<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], mut root: usize, finish: u
 }
 
 
+// 堆排序，不稳定
+pub struct HeapSort;
+
+impl Sorter for HeapSort {
+    fn is_stable(&self) -> bool {
+        false
+    }
+
+    fn sort_with<T, F: Fn(&T, &T) -> bool>(&self, seq: &mut [T], less: &F) {
+        heap_sort(seq, less);
+    }
+}
+
 #[test]
 fn test() {
     // 从大到小排序数字
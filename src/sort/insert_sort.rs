@@ -1,3 +1,5 @@
+use super::sorter::Sorter;
+
 pub fn insert_sort<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], compare: &F) {
     let len = seq.len();
     let mut i = 1;
@@ -14,6 +16,19 @@ pub fn insert_sort<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], compare: &F) {
     }
 }
 
+// 插入排序，只交换相邻的逆序元素，是稳定的
+pub struct InsertSort;
+
+impl Sorter for InsertSort {
+    fn is_stable(&self) -> bool {
+        true
+    }
+
+    fn sort_with<T, F: Fn(&T, &T) -> bool>(&self, seq: &mut [T], less: &F) {
+        insert_sort(seq, less);
+    }
+}
+
 #[test]
 fn test() {
     // 从大到小排序数字
@@ -1,58 +1,265 @@
-pub fn merge_sort<T: Copy, F: Fn(&T, &T) -> bool>(seq: &mut [T], compare: &F) {
+use std::thread;
+use super::sorter::Sorter;
+use super::permute::permute;
+
+// 并行排序时，短于这个长度的子数组不再拆分到新的线程
+const PAR_THRESHOLD: usize = 1 << 13;
+
+// 归并排序的三个版本都只排序下标，最后按下标一次性重排元素
+// 元素不需要 Copy 或 Clone，整个排序只使用一个与下标数组等长的辅助数组
+// 相等时先取左半边的元素，都是稳定的
+
+// 自顶向下的归并排序
+pub fn merge_sort<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], compare: &F) {
+    let n = seq.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    let mut aux = vec![0; n];
+
+    sort(seq, &mut order, &mut aux, compare);
+    permute(seq, &mut order);
+}
+
+// 并行的归并排序，最多使用 threads 个线程，结果与 merge_sort 完全相同
+// 左右两半在不同的线程中排序，归并在当前线程中进行
+pub fn par_merge_sort<T: Sync, F: Fn(&T, &T) -> bool + Sync>(seq: &mut [T], compare: &F, threads: usize) {
+    let n = seq.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    let mut aux = vec![0; n];
+
+    par_sort(seq, &mut order, &mut aux, compare, threads);
+    permute(seq, &mut order);
+}
+
+// 自底向上的归并排序，先两两归并长度为 1 的子数组，再归并长度为 2 的，以此类推
+pub fn merge_sort_bu<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], compare: &F) {
+    let n = seq.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    let mut aux = vec![0; n];
+    let mut width = 1;
+
+    while width < n {
+        let mut lo = 0;
+
+        while lo + width < n {
+            let hi = (lo + 2 * width).min(n);
+            merge(seq, &mut order[lo..hi], &mut aux, width, compare);
+            lo = hi;
+        }
+
+        width *= 2;
+    }
+
+    permute(seq, &mut order);
+}
+
+// 自然的归并排序，找出已经有序的子数组，两两归并，直到只剩一个
+// 输入基本有序时只需要很少的几轮
+pub fn natural_merge_sort<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], compare: &F) {
     let n = seq.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    let mut aux = vec![0; n];
+
+    loop {
+        let mut lo = 0;
+        let mut runs = 0;
+
+        while lo < n {
+            let mid = run_end(seq, &order, lo, compare);
+            let hi = if mid < n { run_end(seq, &order, mid, compare) } else { n };
+
+            if mid < hi {
+                merge(seq, &mut order[lo..hi], &mut aux, mid - lo, compare);
+            }
+
+            lo = hi;
+            runs += 1;
+        }
+
+        if runs <= 1 {
+            break
+        }
+    }
+
+    permute(seq, &mut order);
+}
+
+// 从 lo 开始的有序子数组的结束位置
+fn run_end<T, F: Fn(&T, &T) -> bool>(seq: &[T], order: &[usize], lo: usize, f: &F) -> usize {
+    let mut hi = lo + 1;
+
+    while hi < order.len() && ! f(&seq[order[hi]], &seq[order[hi - 1]]) {
+        hi += 1;
+    }
+
+    hi
+}
+
+fn sort<T, F: Fn(&T, &T) -> bool>(seq: &[T], order: &mut [usize], aux: &mut [usize], f: &F) {
+    let n = order.len();
     let m = n / 2;
 
     if n <= 1 {
         return
     }
 
-    merge_sort(&mut seq[..m], compare);
-    merge_sort(&mut seq[m..], compare);
+    sort(seq, &mut order[..m], aux, f);
+    sort(seq, &mut order[m..], aux, f);
+    merge(seq, order, aux, m, f);
+}
 
-    let mut y = seq.to_vec();
-    merge(&seq[..m], &seq[m..], &mut y, compare);
-    seq.copy_from_slice(&y);
+// 与 sort 的拆分方式相同，线程数量按左右两半平分
+fn par_sort<T, F>(seq: &[T], order: &mut [usize], aux: &mut [usize], f: &F, threads: usize)
+    where T: Sync, F: Fn(&T, &T) -> bool + Sync
+{
+    let n = order.len();
+    let m = n / 2;
+
+    if threads <= 1 || n < PAR_THRESHOLD {
+        sort(seq, order, aux, f);
+        return
+    }
+
+    {
+        let (left, right) = order.split_at_mut(m);
+        let (left_aux, right_aux) = aux[..n].split_at_mut(m);
+
+        thread::scope(|s| {
+            s.spawn(|| par_sort(seq, left, left_aux, f, threads / 2));
+            par_sort(seq, right, right_aux, f, threads - threads / 2);
+        });
+    }
+
+    merge(seq, order, aux, m, f);
 }
 
+// 归并 order[..mid] 和 order[mid..] 两个有序的部分
+fn merge<T, F: Fn(&T, &T) -> bool>(seq: &[T], order: &mut [usize], aux: &mut [usize], mid: usize, f: &F) {
+    // 左半边的最大元素不大于右半边的最小元素时已经有序
+    if ! f(&seq[order[mid]], &seq[order[mid - 1]]) {
+        return
+    }
+
+    let n = order.len();
+    let aux = &mut aux[..n];
+    aux.copy_from_slice(order);
 
-fn merge<T: Copy, F: Fn(&T, &T) -> bool>(x1: &[T], x2: &[T], y: &mut [T], f: &F) {
-    let (mut i, mut j, mut k) = (0, 0, 0);
+    let (mut i, mut j) = (0, mid);
 
-    while i < x1.len() && j < x2.len() {
-        if f(&x1[i], &x2[j]) {
-            y[k] = x1[i];
+    for k in order.iter_mut() {
+        // 相等时先取左半边的元素，保证稳定
+        if j >= n || (i < mid && ! f(&seq[aux[j]], &seq[aux[i]])) {
+            *k = aux[i];
             i += 1;
         }
         else {
-            y[k] = x2[j];
+            *k = aux[j];
             j += 1;
         }
-        k += 1;
     }
+}
+
+// 归并排序，相等时先取左半边的元素，是稳定的
+pub struct MergeSort;
 
-    if i < x1.len() {
-        y[k..].copy_from_slice(&x1[i..]);
+impl Sorter for MergeSort {
+    fn is_stable(&self) -> bool {
+        true
     }
 
-    if j < x2.len() {
-        y[k..].copy_from_slice(&x2[j..]);
+    fn sort_with<T, F: Fn(&T, &T) -> bool>(&self, seq: &mut [T], less: &F) {
+        merge_sort(seq, less);
     }
 }
 
+// 自底向上的归并排序，是稳定的
+pub struct MergeSortBU;
+
+impl Sorter for MergeSortBU {
+    fn is_stable(&self) -> bool {
+        true
+    }
+
+    fn sort_with<T, F: Fn(&T, &T) -> bool>(&self, seq: &mut [T], less: &F) {
+        merge_sort_bu(seq, less);
+    }
+}
+
+// 自然的归并排序，是稳定的
+pub struct NaturalMergeSort;
+
+impl Sorter for NaturalMergeSort {
+    fn is_stable(&self) -> bool {
+        true
+    }
+
+    fn sort_with<T, F: Fn(&T, &T) -> bool>(&self, seq: &mut [T], less: &F) {
+        natural_merge_sort(seq, less);
+    }
+}
 
 #[test]
 fn test() {
+    extern crate rand;
+
+    fn check<S: Fn(&mut [&str], &dyn Fn(&&str, &&str) -> bool)>(sort: S) {
+        // 按字母顺序排序字符串
+        let mut seq = ["beach", "hotel", "airplane", "car", "house", "art"];
+        sort(&mut seq, &|x, y| x < y);
+        assert_eq!(seq, ["airplane", "art", "beach", "car", "hotel", "house"]);
+
+        // 按长度排序字符串，长度相同的保持原来的顺序
+        sort(&mut seq, &|x, y| x.len() < y.len());
+        assert_eq!(seq, ["art", "car", "beach", "hotel", "house", "airplane"]);
+    }
+
+    check(|seq, f| merge_sort(seq, &f));
+    check(|seq, f| merge_sort_bu(seq, &f));
+    check(|seq, f| natural_merge_sort(seq, &f));
+
     // 从大到小排序数字
     let mut seq = [4, 65, 2, -31, 0, 99, 2, 83, 782, 1];
     merge_sort(&mut seq, &|x, y| x > y);
     assert_eq!(seq, [782, 99, 83, 65, 4, 2, 2, 1, 0, -31]);
 
-    // 按字母顺序排序字符串
-    let mut seq = ["beach", "hotel", "airplane", "car", "house", "art"];
-    merge_sort(&mut seq, &|x, y| x < y);
-    assert_eq!(seq, ["airplane", "art", "beach", "car", "hotel", "house"]);
+    // 不能 Copy 的 String，与标准库的稳定排序对比
+    let seq: Vec<String> = (0..1000).map(|i| format!("{}-{}", rand::random::<u8>() % 20, i)).collect();
+    let key = |s: &String| s.split('-').next().unwrap().parse::<u8>().unwrap();
+    let mut expected = seq.clone();
+    expected.sort_by_key(key);
 
-    // 按长度排序字符串
-    merge_sort(&mut seq, &|x, y| x.len() < y.len());
-    assert_eq!(seq, ["car", "art", "house", "hotel", "beach", "airplane"]);
-}
\ No newline at end of file
+    let mut actual = seq.clone();
+    merge_sort(&mut actual, &|x, y| key(x) < key(y));
+    assert_eq!(actual, expected);
+
+    let mut actual = seq.clone();
+    merge_sort_bu(&mut actual, &|x, y| key(x) < key(y));
+    assert_eq!(actual, expected);
+
+    let mut actual = seq.clone();
+    natural_merge_sort(&mut actual, &|x, y| key(x) < key(y));
+    assert_eq!(actual, expected);
+
+    // 已经有序和逆序的输入
+    let mut seq: Vec<u32> = (0..100).collect();
+    natural_merge_sort(&mut seq, &|x, y| x < y);
+    assert_eq!(seq, (0..100).collect::<Vec<_>>());
+
+    natural_merge_sort(&mut seq, &|x, y| x > y);
+    assert_eq!(seq, (0..100).rev().collect::<Vec<_>>());
+
+    let mut seq: [u8; 0] = [];
+    merge_sort_bu(&mut seq, &|x, y| x < y);
+    natural_merge_sort(&mut seq, &|x, y| x < y);
+    par_merge_sort(&mut seq, &|x, y| x < y, 4);
+
+    // 并行排序的结果与串行的完全相同
+    let seq: Vec<(u32, usize)> = (0..100_000).map(|i| (rand::random::<u32>() % 1000, i)).collect();
+    let mut expected = seq.clone();
+    merge_sort(&mut expected, &|x, y| x.0 < y.0);
+
+    for &threads in [1, 2, 4, 7].iter() {
+        let mut actual = seq.clone();
+        par_merge_sort(&mut actual, &|x, y| x.0 < y.0, threads);
+        assert_eq!(actual, expected);
+    }
+}
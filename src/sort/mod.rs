@@ -3,4 +3,11 @@ pub mod bubble_sort;
 pub mod heap_sort;
 pub mod merge_sort;
 pub mod insert_sort;
-pub mod selection_sort;
\ No newline at end of file
+pub mod selection_sort;
+pub mod sorter;
+pub mod shell_sort;
+pub mod counting_sort;
+pub mod bucket_sort;
+pub mod select;
+pub mod external_sort;
+pub mod permute;
\ No newline at end of file
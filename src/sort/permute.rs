@@ -0,0 +1,34 @@
+// 按 order 重排 seq，排序后第 i 个位置是原来的 seq[order[i]]
+// 只排序下标再交换元素，不需要复制或克隆元素
+// 沿着置换的每个环依次交换，order 在结束时变为恒等置换
+pub fn permute<T>(seq: &mut [T], order: &mut [usize]) {
+    for i in 0..seq.len() {
+        let mut cur = i;
+
+        while order[cur] != i {
+            let next = order[cur];
+            seq.swap(cur, next);
+            order[cur] = cur;
+            cur = next;
+        }
+
+        order[cur] = cur;
+    }
+}
+
+#[test]
+fn test() {
+    let mut seq = ["a", "b", "c", "d", "e"];
+    let mut order = [3, 0, 4, 1, 2];
+    permute(&mut seq, &mut order);
+    assert_eq!(seq, ["d", "a", "e", "b", "c"]);
+    assert_eq!(order, [0, 1, 2, 3, 4]);
+
+    // 元素不需要实现 Clone
+    let mut seq = vec![String::from("x"), String::from("y")];
+    permute(&mut seq, &mut [1, 0]);
+    assert_eq!(seq, ["y", "x"]);
+
+    let mut seq: [u8; 0] = [];
+    permute(&mut seq, &mut []);
+}
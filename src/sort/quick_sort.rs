@@ -1,30 +1,156 @@
+use std::thread;
+use super::sorter::Sorter;
+use super::insert_sort::insert_sort;
+use super::heap_sort::heap_sort;
+
+// 小于等于这个长度的子数组切换为插入排序
+const CUTOFF: usize = 10;
+
+// 大于这个长度的子数组用 Tukey's ninther 选择切分元素，否则用三取样
+const NINTHER: usize = 40;
+
+// 并行排序时，短于这个长度的子数组不再拆分到新的线程
+const PAR_THRESHOLD: usize = 1 << 13;
+
+// 三向切分的快速排序
+// 递归深度超过 2 * lg(n) 时改用堆排序，保证最坏情况下也是 n * lg(n)
+// 只递归较短的一边，较长的一边在循环中继续处理，栈的深度不超过 lg(n)
 pub fn quick_sort<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], compare: &F) {
-    let len = seq.len();
+    let limit = 2 * (usize::BITS - seq.len().leading_zeros()) as usize;
+    sort(seq, compare, limit);
+}
+
+// 并行的快速排序，最多使用 threads 个线程
+// 切分方式与 quick_sort 相同，结果也完全相同
+pub fn par_quick_sort<T: Send, F: Fn(&T, &T) -> bool + Sync>(seq: &mut [T], compare: &F, threads: usize) {
+    let limit = 2 * (usize::BITS - seq.len().leading_zeros()) as usize;
+    par_sort(seq, compare, limit, threads);
+}
+
+fn sort<T, F: Fn(&T, &T) -> bool>(mut seq: &mut [T], compare: &F, mut limit: usize) {
+    loop {
+        let len = seq.len();
+
+        if len <= CUTOFF {
+            insert_sort(seq, compare);
+            return
+        }
+
+        if limit == 0 {
+            heap_sort(seq, compare);
+            return
+        }
+
+        limit -= 1;
+
+        let pivot = choose_pivot(seq, compare);
+        seq.swap(0, pivot);
+
+        let (lt, gt) = partition(seq, compare);
+        let (left, rest) = seq.split_at_mut(lt);
+        let right = &mut rest[gt - lt..];
+
+        if left.len() < right.len() {
+            sort(left, compare, limit);
+            seq = right;
+        }
+        else {
+            sort(right, compare, limit);
+            seq = left;
+        }
+    }
+}
+
+// 切分后左右两边在不同的线程中排序，线程数量平分
+fn par_sort<T, F>(seq: &mut [T], compare: &F, limit: usize, threads: usize)
+    where T: Send, F: Fn(&T, &T) -> bool + Sync
+{
+    if threads <= 1 || seq.len() < PAR_THRESHOLD || limit == 0 {
+        sort(seq, compare, limit);
+        return
+    }
+
+    let pivot = choose_pivot(seq, compare);
+    seq.swap(0, pivot);
+
+    let (lt, gt) = partition(seq, compare);
+    let (left, rest) = seq.split_at_mut(lt);
+    let right = &mut rest[gt - lt..];
+
+    thread::scope(|s| {
+        s.spawn(|| par_sort(left, compare, limit - 1, threads / 2));
+        par_sort(right, compare, limit - 1, threads - threads / 2);
+    });
+}
 
-    if len > 1 {
-        let pivot_index = partition(seq, compare);
-        quick_sort(&mut seq[..pivot_index], compare);
-        quick_sort(&mut seq[pivot_index + 1..], compare);
+// 三个位置中元素居中的那个
+fn median3<T, F: Fn(&T, &T) -> bool>(seq: &[T], i: usize, j: usize, k: usize, compare: &F) -> usize {
+    if compare(&seq[i], &seq[j]) {
+        if compare(&seq[j], &seq[k]) { j } else if compare(&seq[i], &seq[k]) { k } else { i }
+    }
+    else {
+        if compare(&seq[k], &seq[j]) { j } else if compare(&seq[k], &seq[i]) { k } else { i }
     }
 }
 
-fn partition<T, F: Fn(&T, &T)  -> bool>(seq: &mut [T], compare: &F) -> usize {
+pub(crate) fn choose_pivot<T, F: Fn(&T, &T) -> bool>(seq: &[T], compare: &F) -> usize {
     let len = seq.len();
-    let mut store_index = 0;
+    let (lo, mid, hi) = (0, len / 2, len - 1);
+
+    if len <= NINTHER {
+        return median3(seq, lo, mid, hi, compare)
+    }
+
+    // 三组三取样的中位数
+    let eps = len / 8;
+    let a = median3(seq, lo, lo + eps, lo + eps + eps, compare);
+    let b = median3(seq, mid - eps, mid, mid + eps, compare);
+    let c = median3(seq, hi - eps - eps, hi - eps, hi, compare);
+
+    median3(seq, a, b, c, compare)
+}
+
+// Dijkstra 三向切分，切分元素在 seq[0]
+// 返回 (lt, gt)，seq[..lt] 小于切分元素，seq[lt..gt] 等于切分元素，seq[gt..] 大于切分元素
+// seq[lt] 始终是一个等于切分元素的元素，不需要复制切分元素
+pub(crate) fn partition<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], compare: &F) -> (usize, usize) {
+    let (mut lt, mut i, mut gt) = (0, 1, seq.len());
 
-    for i in 0..len - 1 {
-        if compare(&seq[i], &seq[len - 1]) {
-            seq.swap(i, store_index);
-            store_index += 1;
+    while i < gt {
+        if compare(&seq[i], &seq[lt]) {
+            seq.swap(lt, i);
+            lt += 1;
+            i += 1;
+        }
+        else if compare(&seq[lt], &seq[i]) {
+            gt -= 1;
+            seq.swap(i, gt);
+        }
+        else {
+            i += 1;
         }
     }
 
-    seq.swap(store_index, len - 1);
-    store_index
+    (lt, gt)
+}
+
+// 快速排序，不稳定
+pub struct QuickSort;
+
+impl Sorter for QuickSort {
+    fn is_stable(&self) -> bool {
+        false
+    }
+
+    fn sort_with<T, F: Fn(&T, &T) -> bool>(&self, seq: &mut [T], less: &F) {
+        quick_sort(seq, less);
+    }
 }
 
 #[test]
 fn test() {
+    extern crate rand;
+
     // 从大到小排序数字
     let mut seq = [4, 65, 2, -31, 0, 99, 2, 83, 782, 1];
     quick_sort(&mut seq, &|x, y| x > y);
@@ -37,5 +163,55 @@ fn test() {
 
     // 按长度排序字符串
     quick_sort(&mut seq, &|x, y| x.len() < y.len());
-    assert_eq!(seq, ["car", "art", "house", "beach", "hotel", "airplane"]);
-}
\ No newline at end of file
+    assert_eq!(seq.iter().map(|s| s.len()).collect::<Vec<_>>(), [3, 3, 5, 5, 5, 8]);
+
+    // 三向切分
+    let mut seq = [3, 1, 3, 2, 3, 3, 0, 3, 5, 3, 4];
+    assert_eq!(partition(&mut seq, &|x, y| x < y), (3, 9));
+    assert_eq!(&seq[3..9], [3, 3, 3, 3, 3, 3]);
+    assert!(seq[..3].iter().all(|&x| x < 3));
+    assert!(seq[9..].iter().all(|&x| x > 3));
+
+    assert_eq!(median3(&[3, 1, 2], 0, 1, 2, &|x, y| x < y), 2);
+    assert_eq!(median3(&[1, 1, 1], 0, 1, 2, &|x, y| x < y), 0);
+
+    // 有序、逆序、全部相等和只有少量不同的键的大数组，都不会退化
+    let n = 100_000;
+    let mut inputs: Vec<Vec<u32>> = vec![
+        (0..n).collect(),
+        (0..n).rev().collect(),
+        vec![7; n as usize],
+        (0..n).map(|_| rand::random::<u32>() % 3).collect(),
+        (0..n).map(|_| rand::random::<u32>()).collect(),
+        // 管风琴形状
+        (0..n / 2).chain((0..n / 2).rev()).collect(),
+    ];
+
+    for seq in inputs.iter_mut() {
+        let mut expected = seq.clone();
+        expected.sort();
+        quick_sort(seq, &|x, y| x < y);
+        assert_eq!(*seq, expected);
+    }
+
+    // 深度用完时改用堆排序
+    let mut seq: Vec<u32> = (0..1000).map(|_| rand::random::<u32>() % 100).collect();
+    let mut expected = seq.clone();
+    expected.sort();
+    sort(&mut seq, &|x, y| x < y, 0);
+    assert_eq!(seq, expected);
+
+    // 并行排序的结果与串行的完全相同，包括相等元素的顺序
+    let seq: Vec<(u32, usize)> = (0..100_000).map(|i| (rand::random::<u32>() % 1000, i)).collect();
+    let mut expected = seq.clone();
+    quick_sort(&mut expected, &|x, y| x.0 < y.0);
+
+    for &threads in [1, 2, 4, 7].iter() {
+        let mut actual = seq.clone();
+        par_quick_sort(&mut actual, &|x, y| x.0 < y.0, threads);
+        assert_eq!(actual, expected);
+    }
+
+    let mut seq: [u8; 0] = [];
+    par_quick_sort(&mut seq, &|x, y| x < y, 4);
+}
@@ -0,0 +1,187 @@
+use super::insert_sort::insert_sort;
+use super::heap_sort::sift_down;
+use super::quick_sort::{choose_pivot, partition};
+
+// 小于等于这个长度的子数组直接用插入排序
+const CUTOFF: usize = 10;
+
+// 快速选择，重新排列 seq 使 seq[k] 为排序后位于 k 的元素
+// 并且 seq[..k] 中的元素都不大于 seq[k]，seq[k + 1..] 中的元素都不小于 seq[k]
+// 切分次数超过 2 * lg(n) 时改用中位数的中位数选择切分元素，保证最坏情况下也是线性的
+pub fn select_nth<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], k: usize, compare: &F) {
+    assert!(k < seq.len(), "index out of bounds: the len is {} but the index is {}", seq.len(), k);
+
+    let mut limit = 2 * (usize::BITS - seq.len().leading_zeros()) as usize;
+    let (mut seq, mut k) = (seq, k);
+
+    loop {
+        if seq.len() <= CUTOFF {
+            insert_sort(seq, compare);
+            return
+        }
+
+        let pivot = if limit > 0 {
+            limit -= 1;
+            choose_pivot(seq, compare)
+        }
+        else {
+            median_of_medians(seq, compare)
+        };
+
+        seq.swap(0, pivot);
+
+        let (lt, gt) = partition(seq, compare);
+
+        if k < lt {
+            seq = &mut seq[..lt];
+        }
+        else if k >= gt {
+            seq = &mut seq[gt..];
+            k -= gt;
+        }
+        else {
+            return
+        }
+    }
+}
+
+// 每 5 个元素一组，把各组的中位数移到 seq 的前面，再选出它们的中位数，返回它的位置
+// 至少有 3 / 10 的元素不大于它，也至少有 3 / 10 的元素不小于它
+fn median_of_medians<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], compare: &F) -> usize {
+    let len = seq.len();
+    let groups = len.div_ceil(5);
+
+    for g in 0..groups {
+        let lo = g * 5;
+        let hi = (lo + 5).min(len);
+        insert_sort(&mut seq[lo..hi], compare);
+        seq.swap(g, lo + (hi - lo) / 2);
+    }
+
+    select_nth(&mut seq[..groups], groups / 2, compare);
+    groups / 2
+}
+
+// 部分排序，seq[..k] 为最小的 k 个元素并且有序，seq[k..] 中元素的顺序不确定
+// 在 seq[..k] 上维护一个最大堆，后面的元素比堆顶小时替换堆顶，最后对堆做堆排序
+pub fn partial_sort<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], k: usize, compare: &F) {
+    let k = k.min(seq.len());
+
+    if k == 0 {
+        return
+    }
+
+    for start in (0..k / 2).rev() {
+        sift_down(seq, start, k - 1, compare);
+    }
+
+    for i in k..seq.len() {
+        if compare(&seq[i], &seq[0]) {
+            seq.swap(0, i);
+            sift_down(seq, 0, k - 1, compare);
+        }
+    }
+
+    for finish in (1..k).rev() {
+        seq.swap(0, finish);
+        sift_down(seq, 0, finish - 1, compare);
+    }
+}
+
+// 迭代器中最小的 k 个元素，按从小到大排列
+// 只保存一个大小为 k 的最大堆，内存与 k 成正比，适合很长甚至无法全部放进内存的序列
+// 需要最大的 k 个元素时，使用相反的 compare
+pub fn top_k<T, I, F>(iter: I, k: usize, compare: &F) -> Vec<T>
+    where I: IntoIterator<Item = T>, F: Fn(&T, &T) -> bool
+{
+    if k == 0 {
+        return Vec::new()
+    }
+
+    let mut iter = iter.into_iter();
+    let mut heap: Vec<T> = iter.by_ref().take(k).collect();
+    let len = heap.len();
+
+    for start in (0..len / 2).rev() {
+        sift_down(&mut heap, start, len - 1, compare);
+    }
+
+    for item in iter {
+        if compare(&item, &heap[0]) {
+            heap[0] = item;
+            sift_down(&mut heap, 0, len - 1, compare);
+        }
+    }
+
+    for finish in (1..len).rev() {
+        heap.swap(0, finish);
+        sift_down(&mut heap, 0, finish - 1, compare);
+    }
+
+    heap
+}
+
+#[test]
+fn test() {
+    extern crate rand;
+
+    // 从大到小第 3 个数字
+    let mut seq = [4, 65, 2, -31, 0, 99, 2, 83, 782, 1];
+    select_nth(&mut seq, 3, &|x, y| x > y);
+    assert_eq!(seq[3], 65);
+    assert!(seq[..3].iter().all(|&x| x >= 65));
+    assert!(seq[4..].iter().all(|&x| x <= 65));
+
+    // 最短的字符串
+    let mut seq = ["beach", "hotel", "airplane", "car", "house", "art"];
+    select_nth(&mut seq, 0, &|x, y| x.len() < y.len());
+    assert_eq!(seq[0].len(), 3);
+
+    // 按字母顺序最小的 3 个字符串
+    let mut seq = ["beach", "hotel", "airplane", "car", "house", "art"];
+    partial_sort(&mut seq, 3, &|x, y| x < y);
+    assert_eq!(seq[..3], ["airplane", "art", "beach"]);
+
+    let mut seq = [3, 1, 2];
+    partial_sort(&mut seq, 10, &|x, y| x < y);
+    assert_eq!(seq, [1, 2, 3]);
+
+    let words = "it was the best of times it was the worst of times".split(' ');
+    assert_eq!(top_k(words.clone(), 4, &|x, y| x > y), ["worst", "was", "was", "times"]);
+    assert_eq!(top_k(words.clone(), 0, &|x, y| x < y), Vec::<&str>::new());
+    assert_eq!(top_k(words, 100, &|x, y| x < y).len(), 12);
+    assert_eq!(top_k(0..1_000_000, 3, &|x, y| x > y), [999_999, 999_998, 999_997]);
+
+    // 大量重复元素和有序的输入
+    for &modulo in [10, 1_000_000].iter() {
+        let seq: Vec<u32> = (0..2000).map(|_| rand::random::<u32>() % modulo).collect();
+        let mut expected = seq.clone();
+        expected.sort();
+
+        for &k in [0, 1, 500, 1000, 1999].iter() {
+            let mut actual = seq.clone();
+            select_nth(&mut actual, k, &|x, y| x < y);
+            assert_eq!(actual[k], expected[k]);
+            assert!(actual[..k].iter().all(|x| *x <= expected[k]));
+            assert!(actual[k + 1..].iter().all(|x| *x >= expected[k]));
+
+            let mut actual = seq.clone();
+            partial_sort(&mut actual, k, &|x, y| x < y);
+            assert_eq!(actual[..k], expected[..k]);
+
+            assert_eq!(top_k(seq.iter().cloned(), k, &|x, y| x < y), expected[..k]);
+        }
+
+        let mut actual = expected.clone();
+        select_nth(&mut actual, 700, &|x, y| x < y);
+        assert_eq!(actual[700], expected[700]);
+    }
+
+    // 直接使用中位数的中位数
+    let mut seq: Vec<u32> = (0..1000).map(|_| rand::random::<u32>() % 1000).collect();
+    let mut expected = seq.clone();
+    expected.sort();
+    let m = median_of_medians(&mut seq, &|x, y| x < y);
+    let rank = expected.iter().filter(|&&x| x < seq[m]).count();
+    assert!((250..750).contains(&rank));
+}
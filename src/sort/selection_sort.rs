@@ -1,3 +1,5 @@
+use super::sorter::Sorter;
+
 pub fn selection_sort<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], compare: &F) {
     let len = seq.len();
     let mut i = 0;
@@ -19,6 +21,19 @@ pub fn selection_sort<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], compare: &F) {
 }
 
 
+// 选择排序，交换可能越过相等的元素，不稳定
+pub struct SelectionSort;
+
+impl Sorter for SelectionSort {
+    fn is_stable(&self) -> bool {
+        false
+    }
+
+    fn sort_with<T, F: Fn(&T, &T) -> bool>(&self, seq: &mut [T], less: &F) {
+        selection_sort(seq, less);
+    }
+}
+
 #[test]
 fn test() {
     // 从大到小排序数字
@@ -0,0 +1,138 @@
+use super::sorter::Sorter;
+
+// 希尔排序的递增序列
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Gaps {
+    // 1, 4, 13, 40, 121, ...，h = 3h + 1
+    Knuth,
+    // 1, 8, 23, 77, 281, ...，4^k + 3 * 2^(k - 1) + 1
+    Sedgewick,
+    // 1, 4, 10, 23, 57, 132, 301, 701, 1750，之后每项乘以 2.25
+    Ciura,
+}
+
+impl Gaps {
+    // 小于 n 的所有间隔，从大到小排列
+    fn sequence(self, n: usize) -> Vec<usize> {
+        let mut gaps = Vec::new();
+
+        match self {
+            Gaps::Knuth => {
+                let mut h = 1;
+
+                while h < n.div_ceil(3).max(2) {
+                    gaps.push(h);
+                    h = 3 * h + 1;
+                }
+            },
+            Gaps::Sedgewick => {
+                gaps.push(1);
+
+                let mut k = 1;
+
+                loop {
+                    let h = (1 << (2 * k)) + 3 * (1 << (k - 1)) + 1;
+
+                    if h >= n {
+                        break
+                    }
+
+                    gaps.push(h);
+                    k += 1;
+                }
+            },
+            Gaps::Ciura => {
+                let mut h = 1;
+
+                for &g in [1, 4, 10, 23, 57, 132, 301, 701, 1750].iter() {
+                    if g >= n && g > 1 {
+                        break
+                    }
+
+                    gaps.push(g);
+                    h = g;
+                }
+
+                while h >= 1750 {
+                    h = h * 9 / 4;
+
+                    if h >= n {
+                        break
+                    }
+
+                    gaps.push(h);
+                }
+            },
+        }
+
+        gaps.reverse();
+        gaps
+    }
+}
+
+// 希尔排序，默认使用 Ciura 序列
+pub fn shell_sort<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], compare: &F) {
+    shell_sort_with(seq, compare, Gaps::Ciura);
+}
+
+// 对每个间隔 h 做 h 路插入排序，最后一轮 h = 1 就是普通的插入排序
+pub fn shell_sort_with<T, F: Fn(&T, &T) -> bool>(seq: &mut [T], compare: &F, gaps: Gaps) {
+    let len = seq.len();
+
+    for h in gaps.sequence(len) {
+        for i in h..len {
+            let mut j = i;
+
+            while j >= h && compare(&seq[j], &seq[j - h]) {
+                seq.swap(j, j - h);
+                j -= h;
+            }
+        }
+    }
+}
+
+// 希尔排序，间隔大于 1 时会越过相等的元素交换，不稳定
+pub struct ShellSort(pub Gaps);
+
+impl Sorter for ShellSort {
+    fn is_stable(&self) -> bool {
+        false
+    }
+
+    fn sort_with<T, F: Fn(&T, &T) -> bool>(&self, seq: &mut [T], less: &F) {
+        shell_sort_with(seq, less, self.0);
+    }
+}
+
+#[test]
+fn test() {
+    extern crate rand;
+
+    // 从大到小排序数字
+    let mut seq = [4, 65, 2, -31, 0, 99, 2, 83, 782, 1];
+    shell_sort(&mut seq, &|x, y| x > y);
+    assert_eq!(seq, [782, 99, 83, 65, 4, 2, 2, 1, 0, -31]);
+
+    // 按字母顺序排序字符串
+    let mut seq = ["beach", "hotel", "airplane", "car", "house", "art"];
+    shell_sort(&mut seq, &|x, y| x < y);
+    assert_eq!(seq, ["airplane", "art", "beach", "car", "hotel", "house"]);
+
+    // 按长度排序字符串
+    shell_sort(&mut seq, &|x, y| x.len() < y.len());
+    assert_eq!(seq.iter().map(|s| s.len()).collect::<Vec<_>>(), [3, 3, 5, 5, 5, 8]);
+
+    assert_eq!(Gaps::Knuth.sequence(100), [13, 4, 1]);
+    assert_eq!(Gaps::Sedgewick.sequence(300), [281, 77, 23, 8, 1]);
+    assert_eq!(Gaps::Ciura.sequence(100), [57, 23, 10, 4, 1]);
+    assert_eq!(Gaps::Ciura.sequence(5000), [3937, 1750, 701, 301, 132, 57, 23, 10, 4, 1]);
+    assert_eq!(Gaps::Knuth.sequence(0), [1]);
+
+    for &gaps in [Gaps::Knuth, Gaps::Sedgewick, Gaps::Ciura].iter() {
+        let mut seq: Vec<u32> = (0..5000).map(|_| rand::random::<u32>() % 1000).collect();
+        let mut expected = seq.clone();
+        expected.sort();
+        shell_sort_with(&mut seq, &|x, y| x < y, gaps);
+        assert_eq!(seq, expected);
+    }
+}
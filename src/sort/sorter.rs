@@ -0,0 +1,104 @@
+// 排序接口
+// 每种排序算法都有一个实现了这个接口的类型，如 QuickSort、MergeSort
+use std::cmp::Ordering;
+
+pub trait Sorter {
+    // 是否稳定，即相等的元素排序后是否保持原来的相对顺序
+    fn is_stable(&self) -> bool;
+
+    // less(x, y) 为 true 时 x 排在 y 前面，与各个排序函数的 compare 参数相同
+    fn sort_with<T, F: Fn(&T, &T) -> bool>(&self, seq: &mut [T], less: &F);
+
+    // 从小到大排序
+    fn sort<T: Ord>(&self, seq: &mut [T]) {
+        self.sort_with(seq, &|x, y| x < y);
+    }
+
+    // 按返回 Ordering 的比较函数排序
+    fn sort_by<T, F: Fn(&T, &T) -> Ordering>(&self, seq: &mut [T], compare: F) {
+        self.sort_with(seq, &|x, y| compare(x, y) == Ordering::Less);
+    }
+
+    // 按 key 从小到大排序
+    fn sort_by_key<T, K: Ord, F: Fn(&T) -> K>(&self, seq: &mut [T], key: F) {
+        self.sort_with(seq, &|x, y| key(x) < key(y));
+    }
+}
+
+// 所有实现共用的一致性测试
+#[cfg(test)]
+fn conformance<S: Sorter>(sorter: S) {
+    extern crate rand;
+
+    let mut seq = [4, 65, 2, -31, 0, 99, 2, 83, 782, 1];
+    sorter.sort(&mut seq);
+    assert_eq!(seq, [-31, 0, 1, 2, 2, 4, 65, 83, 99, 782]);
+
+    sorter.sort_by(&mut seq, |x, y| y.cmp(x));
+    assert_eq!(seq, [782, 99, 83, 65, 4, 2, 2, 1, 0, -31]);
+
+    // 不要求 Copy
+    let mut seq: Vec<String> = ["beach", "hotel", "airplane", "car", "house", "art"].iter().map(|s| s.to_string()).collect();
+    sorter.sort(&mut seq);
+    assert_eq!(seq, ["airplane", "art", "beach", "car", "hotel", "house"]);
+
+    sorter.sort_by_key(&mut seq, |s| s.len());
+    assert_eq!(seq.iter().map(|s| s.len()).collect::<Vec<_>>(), [3, 3, 5, 5, 5, 8]);
+
+    let mut seq: [u8; 0] = [];
+    sorter.sort(&mut seq);
+
+    let mut seq = [1];
+    sorter.sort(&mut seq);
+    assert_eq!(seq, [1]);
+
+    // 随机数据，包含大量重复的键，稳定的排序要保持原来的顺序
+    let mut seq: Vec<(u32, usize)> = (0..500).map(|i| (rand::random::<u32>() % 10, i)).collect();
+    let mut expected = seq.clone();
+    expected.sort_by_key(|&(k, _)| k);
+
+    sorter.sort_by_key(&mut seq, |&(k, _)| k);
+    assert_eq!(seq.iter().map(|&(k, _)| k).collect::<Vec<_>>(), expected.iter().map(|&(k, _)| k).collect::<Vec<_>>());
+
+    if sorter.is_stable() {
+        assert_eq!(seq, expected);
+    }
+}
+
+#[test]
+fn test() {
+    use super::bubble_sort::BubbleSort;
+    use super::insert_sort::InsertSort;
+    use super::selection_sort::SelectionSort;
+    use super::heap_sort::HeapSort;
+    use super::merge_sort::{MergeSort, MergeSortBU, NaturalMergeSort};
+    use super::quick_sort::QuickSort;
+    use super::shell_sort::{ShellSort, Gaps};
+
+    assert!(BubbleSort.is_stable());
+    assert!(InsertSort.is_stable());
+    assert!(! SelectionSort.is_stable());
+    assert!(! HeapSort.is_stable());
+    assert!(MergeSort.is_stable());
+    assert!(MergeSortBU.is_stable());
+    assert!(NaturalMergeSort.is_stable());
+    assert!(! QuickSort.is_stable());
+    assert!(! ShellSort(Gaps::Ciura).is_stable());
+
+    // 选择排序会把后面的最小元素交换到前面，打乱相等元素的顺序
+    let mut seq = [(2, 'a'), (2, 'b'), (1, 'c')];
+    SelectionSort.sort_by_key(&mut seq, |&(k, _)| k);
+    assert_eq!(seq, [(1, 'c'), (2, 'b'), (2, 'a')]);
+
+    conformance(BubbleSort);
+    conformance(InsertSort);
+    conformance(SelectionSort);
+    conformance(HeapSort);
+    conformance(MergeSort);
+    conformance(MergeSortBU);
+    conformance(NaturalMergeSort);
+    conformance(QuickSort);
+    conformance(ShellSort(Gaps::Knuth));
+    conformance(ShellSort(Gaps::Sedgewick));
+    conformance(ShellSort(Gaps::Ciura));
+}
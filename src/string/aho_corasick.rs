@@ -0,0 +1,202 @@
+// Aho-Corasick 多模式匹配
+// 把所有模式放进一棵按 UTF-8 字节建立的单词查找树，再用广度优先搜索为每个结点加上失败链接
+// 失败链接指向当前路径在树中存在的最长真后缀，匹配失败时沿失败链接回退，文本只需扫描一遍
+use std::collections::VecDeque;
+use std::fmt;
+use super::trie_st::TrieST;
+
+struct State {
+    // 子结点按字节有序稀疏存储
+    next: Vec<(u8, usize)>,
+    fail: usize,
+    // 到达这个结点时匹配到的模式，包括沿失败链接可以到达的结点上的模式
+    output: Vec<usize>,
+}
+
+impl State {
+    fn new() -> Self {
+        State { next: Vec::new(), fail: 0, output: Vec::new() }
+    }
+
+    fn goto(&self, c: u8) -> Option<usize> {
+        match self.next.binary_search_by_key(&c, |&(b, _)| b) {
+            Ok(i) => Some(self.next[i].1),
+            Err(_) => None,
+        }
+    }
+}
+
+pub struct AhoCorasick {
+    patterns: Vec<String>,
+    states: Vec<State>,
+}
+
+impl AhoCorasick {
+    // 模式的编号为它在 patterns 中的位置，空模式不参与匹配
+    pub fn new<I, S>(patterns: I) -> Self
+        where I: IntoIterator<Item = S>, S: AsRef<str>
+    {
+        let mut this = AhoCorasick {
+            patterns: patterns.into_iter().map(|p| String::from(p.as_ref())).collect(),
+            states: vec![State::new()],
+        };
+
+        for id in 0..this.patterns.len() {
+            this.insert(id);
+        }
+
+        this.build_fail();
+        this
+    }
+
+    // 以 TrieST 中的所有键为模式，编号为键在 keys() 中的位置
+    pub fn from_trie<T: fmt::Debug>(trie: &TrieST<T>) -> Self {
+        Self::new(trie.keys())
+    }
+
+    fn insert(&mut self, id: usize) {
+        if self.patterns[id].is_empty() {
+            return
+        }
+
+        let mut x = 0;
+
+        for &c in self.patterns[id].as_bytes() {
+            x = match self.states[x].next.binary_search_by_key(&c, |&(b, _)| b) {
+                Ok(i) => self.states[x].next[i].1,
+                Err(i) => {
+                    let v = self.states.len();
+                    self.states.push(State::new());
+                    self.states[x].next.insert(i, (c, v));
+                    v
+                },
+            };
+        }
+
+        self.states[x].output.push(id);
+    }
+
+    // 按广度优先的顺序处理结点，失败链接指向的结点更浅，已经处理完毕
+    fn build_fail(&mut self) {
+        let mut queue = VecDeque::new();
+
+        for &(_, v) in self.states[0].next.iter() {
+            queue.push_back(v);
+        }
+
+        while let Some(u) = queue.pop_front() {
+            for k in 0..self.states[u].next.len() {
+                let (c, v) = self.states[u].next[k];
+                let mut f = self.states[u].fail;
+
+                while f != 0 && self.states[f].goto(c).is_none() {
+                    f = self.states[f].fail;
+                }
+
+                let fail = match self.states[f].goto(c) {
+                    Some(w) if w != v => w,
+                    _ => 0,
+                };
+
+                let inherited = self.states[fail].output.clone();
+                self.states[v].fail = fail;
+                self.states[v].output.extend(inherited);
+                queue.push_back(v);
+            }
+        }
+    }
+
+    // 编号为 id 的模式
+    pub fn pattern(&self, id: usize) -> &str {
+        &self.patterns[id]
+    }
+
+    // 所有模式的所有出现，返回 (模式编号, 字节位置)
+    // 按结束位置排列，结束位置相同时较长的模式在前
+    pub fn find_all(&self, txt: &str) -> Vec<(usize, usize)> {
+        let mut result = Vec::new();
+        let mut x = 0;
+
+        for (i, &c) in txt.as_bytes().iter().enumerate() {
+            while x != 0 && self.states[x].goto(c).is_none() {
+                x = self.states[x].fail;
+            }
+
+            x = self.states[x].goto(c).unwrap_or(0);
+
+            for &id in self.states[x].output.iter() {
+                result.push((id, i + 1 - self.patterns[id].len()));
+            }
+        }
+
+        result
+    }
+
+    // 是否包含任意一个模式
+    pub fn is_match(&self, txt: &str) -> bool {
+        let mut x = 0;
+
+        for &c in txt.as_bytes() {
+            while x != 0 && self.states[x].goto(c).is_none() {
+                x = self.states[x].fail;
+            }
+
+            x = self.states[x].goto(c).unwrap_or(0);
+
+            if ! self.states[x].output.is_empty() {
+                return true
+            }
+        }
+
+        false
+    }
+}
+
+#[test]
+fn test() {
+    extern crate rand;
+
+    let ac = AhoCorasick::new(["he", "she", "his", "hers"]);
+    assert_eq!(ac.find_all("ushers"), [(1, 1), (0, 2), (3, 2)]);
+    assert_eq!(ac.pattern(3), "hers");
+    assert!(ac.is_match("ahisb"));
+    assert!(! ac.is_match("hhh"));
+
+    // 互相包含的模式
+    let ac = AhoCorasick::new(vec!["a", "aa", "aaa", ""]);
+    assert_eq!(ac.find_all("aaa"), [(0, 0), (1, 0), (0, 1), (2, 0), (1, 1), (0, 2)]);
+
+    // 从 TrieST 构造，编号为 keys() 中的位置
+    let mut trie = TrieST::new();
+    trie.put("苹果", 1);
+    trie.put("手机", 2);
+    trie.put("苹果手机", 3);
+
+    let ac = AhoCorasick::from_trie(&trie);
+    let txt = "买苹果手机送手机壳";
+    let found: Vec<(&str, usize)> = ac.find_all(txt).into_iter().map(|(id, i)| (ac.pattern(id), i)).collect();
+    assert_eq!(found, [("苹果", 3), ("苹果手机", 3), ("手机", 9), ("手机", 18)]);
+
+    // 与逐个模式查找对比
+    let patterns: Vec<String> = (0..30).map(|_| {
+        let len = 1 + rand::random::<usize>() % 4;
+        (0..len).map(|_| (b'a' + rand::random::<u8>() % 3) as char).collect()
+    }).collect();
+    let txt: String = (0..500).map(|_| (b'a' + rand::random::<u8>() % 3) as char).collect();
+
+    let ac = AhoCorasick::new(&patterns);
+    let mut actual = ac.find_all(&txt);
+    let mut expected = Vec::new();
+
+    for (id, p) in patterns.iter().enumerate() {
+        for i in 0..txt.len() - p.len() + 1 {
+            if txt[i..].starts_with(p.as_str()) {
+                expected.push((id, i));
+            }
+        }
+    }
+
+    actual.sort();
+    expected.sort();
+    assert_eq!(actual, expected);
+}
@@ -0,0 +1,94 @@
+// Boyer-Moore 子字符串查找（启发式地处理不匹配的字符）
+// 从右往左比较模式，不匹配时根据文本中的字节在模式中最右出现的位置跳跃
+use super::substring_search::SubstringSearch;
+
+const R: usize = 256;
+
+pub struct BoyerMoore {
+    pat: Vec<u8>,
+    // right[c] 为字节 c 在模式中最右出现的位置，不出现时为 -1
+    right: Vec<isize>,
+}
+
+impl BoyerMoore {
+    pub fn new<P: AsRef<[u8]>>(pat: P) -> Self {
+        let pat = pat.as_ref().to_vec();
+        let mut right = vec![-1; R];
+
+        for (j, &c) in pat.iter().enumerate() {
+            right[c as usize] = j as isize;
+        }
+
+        BoyerMoore { pat, right }
+    }
+}
+
+impl SubstringSearch for BoyerMoore {
+    fn pattern_len(&self) -> usize {
+        self.pat.len()
+    }
+
+    fn find(&self, txt: &[u8]) -> Option<usize> {
+        let mut first = None;
+
+        self.scan(txt, |i| {
+            first = Some(i);
+            false
+        });
+
+        first
+    }
+
+    // 匹配后右移一位继续同一遍扫描
+    fn find_all(&self, txt: &[u8]) -> Vec<usize> {
+        let mut result = Vec::new();
+
+        self.scan(txt, |i| {
+            result.push(i);
+            true
+        });
+
+        result
+    }
+}
+
+impl BoyerMoore {
+    // 每找到一个匹配调用 f(位置)，f 返回 false 时停止
+    fn scan<F: FnMut(usize) -> bool>(&self, txt: &[u8], mut f: F) {
+        let (n, m) = (txt.len(), self.pat.len());
+        let mut i = 0;
+
+        while i + m <= n {
+            let mut skip = 0;
+
+            for j in (0..m).rev() {
+                if self.pat[j] != txt[i + j] {
+                    // 至少右移一位
+                    skip = (j as isize - self.right[txt[i + j] as usize]).max(1) as usize;
+                    break
+                }
+            }
+
+            if skip == 0 {
+                if ! f(i) {
+                    return
+                }
+
+                skip = 1;
+            }
+
+            i += skip;
+        }
+    }
+}
+
+#[test]
+fn test() {
+    let bm = BoyerMoore::new("NEEDLE");
+
+    assert_eq!(bm.right[b'N' as usize], 0);
+    assert_eq!(bm.right[b'E' as usize], 5);
+    assert_eq!(bm.right[b'X' as usize], -1);
+    assert_eq!(bm.find(b"FINDINAHAYSTACKNEEDLEINA"), Some(15));
+    assert_eq!(bm.find(b"FINDINAHAYSTACKNEEDINA"), None);
+}
@@ -0,0 +1,97 @@
+// Knuth-Morris-Pratt 子字符串查找
+// 由模式构造确定有限状态自动机，文本指针不回退
+use super::substring_search::SubstringSearch;
+
+const R: usize = 256;
+
+pub struct Kmp {
+    m: usize,
+    // dfa[c][j] 为状态 j 遇到字节 c 后的下一个状态
+    dfa: Vec<Vec<usize>>,
+    // 匹配成功后继续查找时的状态，即整个模式的最长真前缀后缀的长度
+    restart: usize,
+}
+
+impl Kmp {
+    pub fn new<P: AsRef<[u8]>>(pat: P) -> Self {
+        let pat = pat.as_ref();
+        let m = pat.len();
+        let mut dfa = vec![vec![0; m]; R];
+
+        if m > 0 {
+            dfa[pat[0] as usize][0] = 1;
+        }
+
+        // x 为重启状态
+        let mut x = 0;
+
+        for j in 1..m {
+            // 匹配失败时与状态 x 相同
+            for row in dfa.iter_mut() {
+                row[j] = row[x];
+            }
+
+            dfa[pat[j] as usize][j] = j + 1;
+            x = dfa[pat[j] as usize][x];
+        }
+
+        Kmp { m, dfa, restart: x }
+    }
+}
+
+impl SubstringSearch for Kmp {
+    fn pattern_len(&self) -> usize {
+        self.m
+    }
+
+    fn find(&self, txt: &[u8]) -> Option<usize> {
+        if self.m == 0 {
+            return Some(0)
+        }
+
+        let mut j = 0;
+
+        for (i, &c) in txt.iter().enumerate() {
+            j = self.dfa[c as usize][j];
+
+            if j == self.m {
+                return Some(i + 1 - self.m)
+            }
+        }
+
+        None
+    }
+
+    // 匹配成功后回到重启状态，文本指针依然不回退
+    fn find_all(&self, txt: &[u8]) -> Vec<usize> {
+        if self.m == 0 {
+            return (0..txt.len() + 1).collect()
+        }
+
+        let mut result = Vec::new();
+        let mut j = 0;
+
+        for (i, &c) in txt.iter().enumerate() {
+            j = self.dfa[c as usize][j];
+
+            if j == self.m {
+                result.push(i + 1 - self.m);
+                j = self.restart;
+            }
+        }
+
+        result
+    }
+}
+
+#[test]
+fn test() {
+    let kmp = Kmp::new("AACAA");
+
+    assert_eq!(kmp.dfa[b'A' as usize], [1, 2, 2, 4, 5]);
+    assert_eq!(kmp.dfa[b'C' as usize], [0, 0, 3, 0, 0]);
+    assert_eq!(kmp.restart, 2);
+    assert_eq!(kmp.find(b"AABRAACADABRAACAADABRA"), Some(12));
+    assert_eq!(kmp.find(b"AABRAACADABRAACADABRA"), None);
+    assert_eq!(kmp.find_all(b"AACAACAAAACAA"), [0, 3, 8]);
+}
@@ -0,0 +1,62 @@
+// 低位优先的字符串排序，键的长度都为 w
+// 从右往左对第 d 个字节做键索引计数排序，每一轮都是稳定的
+use super::super::sort::permute::permute;
+
+const R: usize = 256;
+
+pub fn lsd_sort<T: AsRef<[u8]>>(seq: &mut [T], w: usize) {
+    let len = seq.len();
+    let mut order: Vec<usize> = (0..len).collect();
+    let mut aux = vec![0; len];
+
+    for d in (0..w).rev() {
+        let mut count = [0; R + 1];
+
+        // 计算出现频率
+        for &i in order.iter() {
+            count[seq[i].as_ref()[d] as usize + 1] += 1;
+        }
+
+        // 将频率转换为索引
+        for r in 0..R {
+            count[r + 1] += count[r];
+        }
+
+        // 将元素分类
+        for &i in order.iter() {
+            let c = seq[i].as_ref()[d] as usize;
+            aux[count[c]] = i;
+            count[c] += 1;
+        }
+
+        order.copy_from_slice(&aux);
+    }
+
+    permute(seq, &mut order);
+}
+
+#[test]
+fn test() {
+    let mut seq = [
+        "4PGC938", "2IYE230", "3CIO720", "1ICK750", "1OHV845", "4JZY524", "1ICK750",
+        "3CIO720", "1OHV845", "1OHV845", "2RLA629", "2RLA629", "3ATW723",
+    ];
+
+    lsd_sort(&mut seq, 7);
+    assert_eq!(seq, [
+        "1ICK750", "1ICK750", "1OHV845", "1OHV845", "1OHV845", "2IYE230", "2RLA629",
+        "2RLA629", "3ATW723", "3CIO720", "3CIO720", "4JZY524", "4PGC938",
+    ]);
+
+    // 只比较前 w 个字节，相同的键保持原来的顺序
+    let mut seq: Vec<String> = ["ba2", "ab1", "ba1", "ab2"].iter().map(|s| s.to_string()).collect();
+    lsd_sort(&mut seq, 2);
+    assert_eq!(seq, ["ab1", "ab2", "ba2", "ba1"]);
+
+    let mut seq: [&[u8]; 4] = [&[3, 0], &[255, 1], &[0, 9], &[3, 0]];
+    lsd_sort(&mut seq, 2);
+    assert_eq!(seq, [&[0, 9], &[3, 0], &[3, 0], &[255, 1]]);
+
+    let mut seq: [&str; 0] = [];
+    lsd_sort(&mut seq, 3);
+}
@@ -1 +1,12 @@
-pub mod trie_st;
\ No newline at end of file
+pub mod trie_st;
+pub mod tst;
+pub mod lsd_sort;
+pub mod msd_sort;
+pub mod quick3_string;
+pub mod substring_search;
+pub mod kmp;
+pub mod boyer_moore;
+pub mod rabin_karp;
+pub mod nfa;
+pub mod suffix_array;
+pub mod aho_corasick;
\ No newline at end of file
@@ -0,0 +1,118 @@
+// 高位优先的字符串排序
+// 按第 d 个字节做键索引计数排序，再递归地排序每个字节相同的子数组
+// 字符串结尾看作比任何字节都小，小数组切换为插入排序
+use super::super::sort::permute::permute;
+
+const R: usize = 256;
+const CUTOFF: usize = 15;
+
+pub fn msd_sort<T: AsRef<[u8]>>(seq: &mut [T]) {
+    let len = seq.len();
+    let mut order: Vec<usize> = (0..len).collect();
+    let mut aux = vec![0; len];
+
+    sort(seq, &mut order, &mut aux, 0);
+    permute(seq, &mut order);
+}
+
+// 第 d 个字节，到达结尾时返回 0，其余字节加一
+fn char_at(s: &[u8], d: usize) -> usize {
+    match s.get(d) {
+        Some(&c) => c as usize + 1,
+        None => 0,
+    }
+}
+
+fn sort<T: AsRef<[u8]>>(seq: &[T], order: &mut [usize], aux: &mut [usize], d: usize) {
+    let len = order.len();
+
+    if len <= CUTOFF {
+        insert_sort(seq, order, d);
+        return
+    }
+
+    let mut count = [0; R + 2];
+
+    for &i in order.iter() {
+        count[char_at(seq[i].as_ref(), d) + 1] += 1;
+    }
+
+    for r in 0..R + 1 {
+        count[r + 1] += count[r];
+    }
+
+    // 分类后 count[r] 为字节 r 的子数组的起点
+    let start = count;
+
+    for &i in order.iter() {
+        let c = char_at(seq[i].as_ref(), d);
+        aux[count[c]] = i;
+        count[c] += 1;
+    }
+
+    order.copy_from_slice(&aux[..len]);
+
+    // 已经到达结尾的字符串（r = 0）无需继续排序
+    for r in 1..R + 1 {
+        let (lo, hi) = (start[r], count[r]);
+
+        if hi - lo > 1 {
+            sort(seq, &mut order[lo..hi], aux, d + 1);
+        }
+    }
+}
+
+// 前 d 个字节都相同，从第 d 个字节开始比较
+fn insert_sort<T: AsRef<[u8]>>(seq: &[T], order: &mut [usize], d: usize) {
+    for i in 1..order.len() {
+        let mut j = i;
+
+        while j > 0 && seq[order[j]].as_ref()[d..] < seq[order[j - 1]].as_ref()[d..] {
+            order.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+#[test]
+fn test() {
+    extern crate rand;
+
+    let mut seq = [
+        "she", "sells", "seashells", "by", "the", "sea", "shore",
+        "the", "shells", "she", "sells", "are", "surely", "seashells",
+    ];
+
+    msd_sort(&mut seq);
+    assert_eq!(seq, [
+        "are", "by", "sea", "seashells", "seashells", "sells", "sells",
+        "she", "she", "shells", "shore", "surely", "the", "the",
+    ]);
+
+    // 大数组走计数排序，与标准库的排序对比
+    let mut seq: Vec<String> = (0..2000).map(|_| {
+        let len = rand::random::<usize>() % 8;
+        (0..len).map(|_| (b'a' + rand::random::<u8>() % 3) as char).collect()
+    }).collect();
+
+    let mut expected = seq.clone();
+    expected.sort();
+    msd_sort(&mut seq);
+    assert_eq!(seq, expected);
+
+    // 字节串，包含 0 和 255
+    let mut seq: Vec<Vec<u8>> = (0..500).map(|_| {
+        let len = rand::random::<usize>() % 4;
+        (0..len).map(|_| [0, 1, 254, 255][rand::random::<usize>() % 4]).collect()
+    }).collect();
+
+    let mut expected = seq.clone();
+    expected.sort();
+
+    let mut slices: Vec<&[u8]> = seq.iter().map(|s| s.as_slice()).collect();
+    msd_sort(&mut slices);
+    assert_eq!(slices, expected);
+
+    msd_sort(&mut seq);
+    assert_eq!(seq, expected);
+}
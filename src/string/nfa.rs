@@ -0,0 +1,443 @@
+// 基于非确定有限状态自动机的正则表达式
+// 支持连接、|、*、+、?、. 、字符类 [a-z] [^0-9]、括号分组和 \ 转义
+// 模式编译为一组状态，状态 i 的匹配转换由第 i 个元素决定，ε 转换保存在有向图中
+// 模拟时同时维护所有可达的状态，匹配时间与文本长度和模式长度的乘积成正比
+use std::error;
+use std::fmt;
+use super::super::graph::digraph::Digraph;
+
+#[derive(Debug, PartialEq)]
+pub enum RegexError {
+    // 没有匹配的括号，值为它在模式中的位置（字符数）
+    UnmatchedParen(usize),
+    // 字符类没有结束的 ]
+    UnclosedClass(usize),
+    // 字符类中的范围起点大于终点，如 [z-a]
+    InvalidRange(usize),
+    // 量词前面没有可以重复的内容，如 *a、(|a)、a**
+    NothingToRepeat(usize),
+    // 模式以单个 \ 结尾
+    TrailingEscape,
+}
+
+impl fmt::Display for RegexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RegexError::UnmatchedParen(i) => write!(f, "unmatched parenthesis at {}", i),
+            RegexError::UnclosedClass(i) => write!(f, "unclosed character class at {}", i),
+            RegexError::InvalidRange(i) => write!(f, "invalid character range at {}", i),
+            RegexError::NothingToRepeat(i) => write!(f, "nothing to repeat at {}", i),
+            RegexError::TrailingEscape => write!(f, "trailing backslash"),
+        }
+    }
+}
+
+impl error::Error for RegexError {}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Char(char),
+    Any,
+    // 字符类，范围都是闭区间
+    Class(Vec<(char, char)>, bool),
+    LeftParen,
+    RightParen,
+    Or,
+    Star,
+    Plus,
+    Question,
+}
+
+impl Token {
+    // 能否匹配字符 c，元字符不匹配任何字符
+    fn matches(&self, c: char) -> bool {
+        match *self {
+            Token::Char(x) => x == c,
+            Token::Any => true,
+            Token::Class(ref ranges, negated) => {
+                ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi) != negated
+            },
+            _ => false,
+        }
+    }
+
+    fn is_quantifier(&self) -> bool {
+        matches!(*self, Token::Star | Token::Plus | Token::Question)
+    }
+}
+
+#[derive(Debug)]
+pub struct NFA {
+    re: Vec<Token>,
+    // ε 转换，顶点 re.len() 为接受状态
+    g: Digraph,
+}
+
+impl NFA {
+    pub fn new(pattern: &str) -> Result<Self, RegexError> {
+        let (tokens, mut pos) = parse(pattern)?;
+
+        // 整个模式外加一对括号，顶层的 | 与括号内的处理方式相同
+        let mut re = vec![Token::LeftParen];
+        re.extend(tokens);
+        re.push(Token::RightParen);
+        pos.insert(0, 0);
+        pos.push(pattern.chars().count());
+
+        let m = re.len();
+        let mut g = Digraph::with_capacity(m + 1);
+        let mut ops = Vec::new();
+
+        for i in 0..m {
+            let mut lp = i;
+
+            match re[i] {
+                Token::LeftParen | Token::Or => ops.push(i),
+                Token::RightParen => {
+                    let mut ors = Vec::new();
+
+                    loop {
+                        let op = ops.pop().ok_or(RegexError::UnmatchedParen(pos[i]))?;
+
+                        if re[op] == Token::Or {
+                            ors.push(op);
+                        }
+                        else {
+                            lp = op;
+                            break
+                        }
+                    }
+
+                    // 模式中的右括号与外加的左括号配对，说明右括号多余
+                    // 外加的右括号与模式中的左括号配对，说明左括号多余
+                    if lp == 0 && i != m - 1 {
+                        return Err(RegexError::UnmatchedParen(pos[i]))
+                    }
+
+                    if lp != 0 && i == m - 1 {
+                        return Err(RegexError::UnmatchedParen(pos[lp]))
+                    }
+
+                    for or in ors {
+                        g.add_edge(lp, or + 1);
+                        g.add_edge(or, i);
+                    }
+                },
+                _ => {},
+            }
+
+            // 量词作用于前面的单个元素或整个括号
+            if i + 1 < m {
+                match re[i + 1] {
+                    Token::Star => {
+                        g.add_edge(lp, i + 1);
+                        g.add_edge(i + 1, lp);
+                    },
+                    Token::Plus => g.add_edge(i + 1, lp),
+                    Token::Question => g.add_edge(lp, i + 1),
+                    _ => {},
+                }
+            }
+
+            match re[i] {
+                Token::LeftParen | Token::RightParen | Token::Star | Token::Plus | Token::Question => {
+                    g.add_edge(i, i + 1);
+                },
+                _ => {},
+            }
+        }
+
+        Ok(NFA { re, g })
+    }
+
+    // 从 sources 出发经过 ε 转换可以到达的所有状态
+    fn closure(&self, sources: &[usize]) -> Vec<usize> {
+        let mut marked = vec![false; self.g.v()];
+        let mut stack = sources.to_vec();
+        let mut states = Vec::new();
+
+        while let Some(v) = stack.pop() {
+            if marked[v] {
+                continue
+            }
+
+            marked[v] = true;
+            states.push(v);
+
+            for &w in self.g.adj(v) {
+                if ! marked[w] {
+                    stack.push(w);
+                }
+            }
+        }
+
+        states
+    }
+
+    // 读入字符 c 后经过匹配转换到达的状态
+    fn step(&self, states: &[usize], c: char) -> Vec<usize> {
+        states.iter()
+            .filter(|&&v| v < self.re.len() && self.re[v].matches(c))
+            .map(|&v| v + 1)
+            .collect()
+    }
+
+    fn accepts(&self, states: &[usize]) -> bool {
+        states.contains(&self.re.len())
+    }
+
+    // 整个文本是否匹配模式
+    pub fn is_match(&self, txt: &str) -> bool {
+        let mut states = self.closure(&[0]);
+
+        for c in txt.chars() {
+            states = self.closure(&self.step(&states, c));
+
+            if states.is_empty() {
+                return false
+            }
+        }
+
+        self.accepts(&states)
+    }
+
+    // 从 start 开始的最长匹配的结束位置
+    fn longest_at(&self, txt: &str, start: usize) -> Option<usize> {
+        let mut states = self.closure(&[0]);
+        let mut end = if self.accepts(&states) { Some(start) } else { None };
+
+        for (i, c) in txt[start..].char_indices() {
+            states = self.closure(&self.step(&states, c));
+
+            if states.is_empty() {
+                break
+            }
+
+            if self.accepts(&states) {
+                end = Some(start + i + c.len_utf8());
+            }
+        }
+
+        end
+    }
+
+    // 最左边的最长匹配，返回字节位置 [start, end)
+    pub fn find(&self, txt: &str) -> Option<(usize, usize)> {
+        self.find_from(txt, 0)
+    }
+
+    fn find_from(&self, txt: &str, from: usize) -> Option<(usize, usize)> {
+        txt[from..].char_indices()
+            .map(|(i, _)| from + i)
+            .chain(Some(txt.len()))
+            .filter_map(|start| self.longest_at(txt, start).map(|end| (start, end)))
+            .next()
+    }
+
+    // 所有互不重叠的匹配
+    // 空匹配之后跳过一个字符，避免在同一位置重复匹配
+    pub fn find_all(&self, txt: &str) -> Vec<(usize, usize)> {
+        let mut result = Vec::new();
+        let mut from = 0;
+
+        while from <= txt.len() {
+            let (start, end) = match self.find_from(txt, from) {
+                Some(span) => span,
+                None => break,
+            };
+
+            result.push((start, end));
+
+            from = if end > start {
+                end
+            }
+            else {
+                match txt[end..].chars().next() {
+                    Some(c) => end + c.len_utf8(),
+                    None => break,
+                }
+            };
+        }
+
+        result
+    }
+}
+
+// 把模式解析为元素序列，同时返回每个元素在模式中的位置（字符数）
+fn parse(pattern: &str) -> Result<(Vec<Token>, Vec<usize>), RegexError> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut re = Vec::new();
+    let mut pos = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        pos.push(i);
+
+        let token = match chars[i] {
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            '|' => Token::Or,
+            '.' => Token::Any,
+            '*' => Token::Star,
+            '+' => Token::Plus,
+            '?' => Token::Question,
+            '\\' => {
+                i += 1;
+                Token::Char(*chars.get(i).ok_or(RegexError::TrailingEscape)?)
+            },
+            '[' => {
+                let (token, next) = parse_class(&chars, i)?;
+                i = next;
+                token
+            },
+            c => Token::Char(c),
+        };
+
+        // 量词必须跟在字符、字符类、右括号或者 . 之后
+        if token.is_quantifier() {
+            match re.last() {
+                None | Some(&Token::LeftParen) | Some(&Token::Or) => {
+                    return Err(RegexError::NothingToRepeat(i))
+                },
+                Some(last) if last.is_quantifier() => {
+                    return Err(RegexError::NothingToRepeat(i))
+                },
+                _ => {},
+            }
+        }
+
+        re.push(token);
+        i += 1;
+    }
+
+    Ok((re, pos))
+}
+
+// 解析从 chars[start] = '[' 开始的字符类，返回字符类和 ']' 的位置
+fn parse_class(chars: &[char], start: usize) -> Result<(Token, usize), RegexError> {
+    let mut i = start + 1;
+    let mut ranges = Vec::new();
+    let negated = chars.get(i) == Some(&'^');
+
+    if negated {
+        i += 1;
+    }
+
+    loop {
+        let lo = match chars.get(i) {
+            None => return Err(RegexError::UnclosedClass(start)),
+            // 紧跟在 [ 或 [^ 之后的 ] 当作普通字符
+            Some(&']') if ! ranges.is_empty() => return Ok((Token::Class(ranges, negated), i)),
+            Some(&'\\') => {
+                i += 1;
+                *chars.get(i).ok_or(RegexError::TrailingEscape)?
+            },
+            Some(&c) => c,
+        };
+
+        // a-z 形式的范围，结尾的 - 当作普通字符
+        let hi = if chars.get(i + 1) == Some(&'-') && chars.get(i + 2).is_some_and(|&c| c != ']') {
+            i += 2;
+
+            let hi = match chars[i] {
+                '\\' => {
+                    i += 1;
+                    *chars.get(i).ok_or(RegexError::TrailingEscape)?
+                },
+                c => c,
+            };
+
+            if hi < lo {
+                return Err(RegexError::InvalidRange(i))
+            }
+
+            hi
+        }
+        else {
+            lo
+        };
+
+        ranges.push((lo, hi));
+        i += 1;
+    }
+}
+
+#[test]
+fn test() {
+    let nfa = NFA::new("((A*B|AC)D)").unwrap();
+    assert!(nfa.is_match("AABD"));
+    assert!(nfa.is_match("ACD"));
+    assert!(nfa.is_match("BD"));
+    assert!(! nfa.is_match("AAAC"));
+    assert!(! nfa.is_match("AABDX"));
+
+    // 多路或
+    let nfa = NFA::new("cat|dog|bird").unwrap();
+    assert!(nfa.is_match("cat"));
+    assert!(nfa.is_match("dog"));
+    assert!(nfa.is_match("bird"));
+    assert!(! nfa.is_match("catdog"));
+    assert!(! nfa.is_match(""));
+
+    // + 和 ?
+    let nfa = NFA::new("ab+c?").unwrap();
+    assert!(nfa.is_match("ab"));
+    assert!(nfa.is_match("abbbc"));
+    assert!(! nfa.is_match("a"));
+    assert!(! nfa.is_match("abcc"));
+
+    let nfa = NFA::new("(ab)+|x?").unwrap();
+    assert!(nfa.is_match("ababab"));
+    assert!(nfa.is_match(""));
+    assert!(nfa.is_match("x"));
+    assert!(! nfa.is_match("aba"));
+
+    // 字符类
+    let nfa = NFA::new("[a-c0-9_]+[^a-z]").unwrap();
+    assert!(nfa.is_match("a1_c!"));
+    assert!(nfa.is_match("bA"));
+    assert!(! nfa.is_match("ab"));
+    assert!(! nfa.is_match("dA"));
+
+    let nfa = NFA::new("[]-]*").unwrap();
+    assert!(nfa.is_match("]-]"));
+
+    // 转义和 .
+    let nfa = NFA::new(r"a\.b.c\*").unwrap();
+    assert!(nfa.is_match("a.bxc*"));
+    assert!(! nfa.is_match("axbxc*"));
+
+    // Unicode
+    let nfa = NFA::new("苹果(手机|电脑)?").unwrap();
+    assert!(nfa.is_match("苹果"));
+    assert!(nfa.is_match("苹果电脑"));
+    assert!(! nfa.is_match("苹果手表"));
+
+    // 匹配的位置，最左边的最长匹配
+    let nfa = NFA::new("[0-9]+").unwrap();
+    assert_eq!(nfa.find("order 42 and 7"), Some((6, 8)));
+    assert_eq!(nfa.find_all("order 42 and 7"), [(6, 8), (13, 14)]);
+    assert_eq!(nfa.find("none"), None);
+
+    let nfa = NFA::new("苹果+").unwrap();
+    let txt = "买苹果果和苹果";
+    assert_eq!(nfa.find_all(txt), [(3, 12), (15, 21)]);
+    assert_eq!(&txt[3..12], "苹果果");
+
+    // 空匹配
+    let nfa = NFA::new("a*").unwrap();
+    assert_eq!(nfa.find("baa"), Some((0, 0)));
+    assert_eq!(nfa.find_all("baa"), [(0, 0), (1, 3), (3, 3)]);
+
+    // 错误的模式
+    assert_eq!(NFA::new("(ab").unwrap_err(), RegexError::UnmatchedParen(0));
+    assert_eq!(NFA::new("a(b(c)").unwrap_err(), RegexError::UnmatchedParen(1));
+    assert_eq!(NFA::new("ab)").unwrap_err(), RegexError::UnmatchedParen(2));
+    assert_eq!(NFA::new("[abc").unwrap_err(), RegexError::UnclosedClass(0));
+    assert_eq!(NFA::new("[z-a]").unwrap_err(), RegexError::InvalidRange(3));
+    assert_eq!(NFA::new("*a").unwrap_err(), RegexError::NothingToRepeat(0));
+    assert_eq!(NFA::new("(|+)").unwrap_err(), RegexError::NothingToRepeat(2));
+    assert_eq!(NFA::new("a**").unwrap_err(), RegexError::NothingToRepeat(2));
+    assert_eq!(NFA::new("a\\").unwrap_err(), RegexError::TrailingEscape);
+    assert_eq!(format!("{}", RegexError::UnmatchedParen(3)), "unmatched parenthesis at 3");
+}
@@ -0,0 +1,104 @@
+// 三向字符串快速排序
+// 按第 d 个字节将数组切分为小于、等于和大于切分字节的三部分
+// 只有等于的部分继续比较下一个字节，适合含有较长公共前缀的键
+const CUTOFF: usize = 15;
+
+pub fn quick3_string<T: AsRef<[u8]>>(seq: &mut [T]) {
+    sort(seq, 0);
+}
+
+// 第 d 个字节，到达结尾时返回 None，比任何字节都小
+fn char_at(s: &[u8], d: usize) -> Option<u8> {
+    s.get(d).cloned()
+}
+
+fn sort<T: AsRef<[u8]>>(seq: &mut [T], d: usize) {
+    let len = seq.len();
+
+    if len <= CUTOFF {
+        insert_sort(seq, d);
+        return
+    }
+
+    let (mut lt, mut gt) = (0, len - 1);
+    let v = char_at(seq[0].as_ref(), d);
+    let mut i = 1;
+
+    while i <= gt {
+        let t = char_at(seq[i].as_ref(), d);
+
+        if t < v {
+            seq.swap(lt, i);
+            lt += 1;
+            i += 1;
+        }
+        else if t > v {
+            seq.swap(i, gt);
+            gt -= 1;
+        }
+        else {
+            i += 1;
+        }
+    }
+
+    // seq[..lt] < v = seq[lt..=gt] < seq[gt + 1..]
+    sort(&mut seq[..lt], d);
+
+    if v.is_some() {
+        sort(&mut seq[lt..gt + 1], d + 1);
+    }
+
+    sort(&mut seq[gt + 1..], d);
+}
+
+// 前 d 个字节都相同，从第 d 个字节开始比较
+fn insert_sort<T: AsRef<[u8]>>(seq: &mut [T], d: usize) {
+    for i in 1..seq.len() {
+        let mut j = i;
+
+        while j > 0 && seq[j].as_ref()[d..] < seq[j - 1].as_ref()[d..] {
+            seq.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+#[test]
+fn test() {
+    extern crate rand;
+
+    let mut seq = [
+        "she", "sells", "seashells", "by", "the", "sea", "shore",
+        "the", "shells", "she", "sells", "are", "surely", "seashells",
+    ];
+
+    quick3_string(&mut seq);
+    assert_eq!(seq, [
+        "are", "by", "sea", "seashells", "seashells", "sells", "sells",
+        "she", "she", "shells", "shore", "surely", "the", "the",
+    ]);
+
+    // 大量公共前缀的键
+    let mut seq: Vec<String> = (0..2000).map(|_| {
+        let len = rand::random::<usize>() % 8;
+        let tail: String = (0..len).map(|_| (b'a' + rand::random::<u8>() % 3) as char).collect();
+        format!("2018-01-01 {}", tail)
+    }).collect();
+
+    let mut expected = seq.clone();
+    expected.sort();
+    quick3_string(&mut seq);
+    assert_eq!(seq, expected);
+
+    let seq: Vec<Vec<u8>> = (0..500).map(|_| {
+        let len = rand::random::<usize>() % 4;
+        (0..len).map(|_| [0, 1, 254, 255][rand::random::<usize>() % 4]).collect()
+    }).collect();
+
+    let mut expected = seq.clone();
+    expected.sort();
+
+    let mut slices: Vec<&[u8]> = seq.iter().map(|s| s.as_slice()).collect();
+    quick3_string(&mut slices);
+    assert_eq!(slices, expected);
+}
@@ -0,0 +1,209 @@
+// Rabin-Karp 指纹字符串查找
+// 比较模式和文本中长度为 m 的子串的散列值，散列值可以在文本上滚动计算
+// 蒙特卡洛版本散列值相等即认为匹配，模数在 2^60 以上，每个位置误判的概率约为 2^-60
+// 拉斯维加斯版本散列值相等时再逐个比较字节，结果一定正确
+extern crate rand;
+
+use super::substring_search::SubstringSearch;
+
+const R: u64 = 256;
+
+pub struct RabinKarp {
+    pat: Vec<u8>,
+    pat_hash: u64,
+    // 一个随机的大素数
+    q: u64,
+    // R^(m-1) % q
+    rm: u64,
+    las_vegas: bool,
+}
+
+impl RabinKarp {
+    // 蒙特卡洛版本
+    pub fn new<P: AsRef<[u8]>>(pat: P) -> Self {
+        let pat = pat.as_ref().to_vec();
+        let q = random_prime();
+        let mut rm = 1;
+
+        for _ in 1..pat.len() {
+            rm = mul_mod(R, rm, q);
+        }
+
+        let pat_hash = hash(&pat, q);
+
+        RabinKarp { pat, pat_hash, q, rm, las_vegas: false }
+    }
+
+    // 拉斯维加斯版本
+    pub fn las_vegas<P: AsRef<[u8]>>(pat: P) -> Self {
+        let mut this = Self::new(pat);
+        this.las_vegas = true;
+        this
+    }
+
+    fn check(&self, txt: &[u8], i: usize) -> bool {
+        ! self.las_vegas || txt[i..i + self.pat.len()] == self.pat[..]
+    }
+}
+
+// a * b % q，乘积用 u128 计算，不会溢出
+fn mul_mod(a: u64, b: u64, q: u64) -> u64 {
+    (a as u128 * b as u128 % q as u128) as u64
+}
+
+fn pow_mod(mut a: u64, mut e: u64, q: u64) -> u64 {
+    let mut result = 1;
+
+    while e > 0 {
+        if e & 1 == 1 {
+            result = mul_mod(result, a, q);
+        }
+
+        a = mul_mod(a, a, q);
+        e >>= 1;
+    }
+
+    result
+}
+
+// 用 Horner 方法计算 key 的散列值
+fn hash(key: &[u8], q: u64) -> u64 {
+    key.iter().fold(0, |h, &c| (mul_mod(R, h, q) + c as u64) % q)
+}
+
+// [2^60, 2^61) 之间的随机素数，q 加上一个字节不会溢出 u64
+fn random_prime() -> u64 {
+    loop {
+        let q = (1 << 60) + rand::random::<u64>() % (1 << 60);
+
+        if is_prime(q) {
+            return q
+        }
+    }
+}
+
+// 以前 12 个素数为底的 Miller-Rabin 测试，对所有 u64 都是确定的
+fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    for &p in BASES.iter() {
+        if n % p == 0 {
+            return n == p
+        }
+    }
+
+    if n < 2 {
+        return false
+    }
+
+    // n - 1 = d * 2^s，d 为奇数
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    'witness: for &a in BASES.iter() {
+        let mut x = pow_mod(a, d, n);
+
+        if x == 1 || x == n - 1 {
+            continue
+        }
+
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+
+            if x == n - 1 {
+                continue 'witness
+            }
+        }
+
+        return false
+    }
+
+    true
+}
+
+impl SubstringSearch for RabinKarp {
+    fn pattern_len(&self) -> usize {
+        self.pat.len()
+    }
+
+    fn find(&self, txt: &[u8]) -> Option<usize> {
+        let mut first = None;
+
+        self.scan(txt, |i| {
+            first = Some(i);
+            false
+        });
+
+        first
+    }
+
+    // 匹配后继续滚动散列值，不重新计算窗口的散列值
+    fn find_all(&self, txt: &[u8]) -> Vec<usize> {
+        if self.pat.is_empty() {
+            return (0..txt.len() + 1).collect()
+        }
+
+        let mut result = Vec::new();
+
+        self.scan(txt, |i| {
+            result.push(i);
+            true
+        });
+
+        result
+    }
+}
+
+impl RabinKarp {
+    // 在 txt 上滚动散列值，每找到一个匹配调用 f(位置)，f 返回 false 时停止
+    fn scan<F: FnMut(usize) -> bool>(&self, txt: &[u8], mut f: F) {
+        let (n, m, q) = (txt.len(), self.pat.len(), self.q);
+
+        if n < m {
+            return
+        }
+
+        let mut txt_hash = hash(&txt[..m], q);
+
+        if txt_hash == self.pat_hash && self.check(txt, 0) && ! f(0) {
+            return
+        }
+
+        for i in m..n {
+            // 减去第一个字节，加上最后一个字节
+            txt_hash = (txt_hash + q - mul_mod(self.rm, txt[i - m] as u64, q)) % q;
+            txt_hash = (mul_mod(txt_hash, R, q) + txt[i] as u64) % q;
+
+            let offset = i + 1 - m;
+
+            if txt_hash == self.pat_hash && self.check(txt, offset) && ! f(offset) {
+                return
+            }
+        }
+    }
+}
+
+#[test]
+fn test() {
+    assert!(is_prime(2));
+    assert!(is_prime(997));
+    assert!(! is_prime(1));
+    assert!(! is_prime(1001));
+    assert!(! is_prime(0));
+    // Carmichael 数和强伪素数
+    assert!(! is_prime(561));
+    assert!(! is_prime(3_215_031_751));
+    assert!(is_prime((1 << 61) - 1));
+    assert!(! is_prime(((1 << 31) - 1) * ((1 << 29) - 3)));
+
+    let q = random_prime();
+    assert!((1 << 60..1 << 61).contains(&q) && is_prime(q));
+    assert_eq!(pow_mod(3, 200, 1_000_000_007), 136_318_165);
+
+    let rk = RabinKarp::new("26535");
+    assert_eq!(rk.find(b"3141592653589793"), Some(6));
+    assert_eq!(rk.find(b"31415926358979"), None);
+
+    let rk = RabinKarp::las_vegas("26535");
+    assert_eq!(rk.find(b"3141592653589793"), Some(6));
+}
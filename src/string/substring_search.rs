@@ -0,0 +1,185 @@
+// 子字符串查找
+// Kmp、BoyerMoore 和 RabinKarp 都实现了这个接口，位置都是字节偏移量
+use std::io::{self, Read};
+
+// 流式查找时每次读取的字节数
+const CHUNK: usize = 8192;
+
+pub trait SubstringSearch {
+    // 模式的长度
+    fn pattern_len(&self) -> usize;
+
+    // 模式在 txt 中第一次出现的位置
+    fn find(&self, txt: &[u8]) -> Option<usize>;
+
+    // 模式在 txt 中所有出现的位置，包括相互重叠的
+    // 每个实现都在同一遍扫描中继续查找，不从头开始
+    fn find_all(&self, txt: &[u8]) -> Vec<usize>;
+
+    // 在输入流中查找第一次出现的位置
+    fn find_in<R: Read>(&self, reader: R) -> io::Result<Option<usize>>
+        where Self: Sized
+    {
+        if self.pattern_len() == 0 {
+            return Ok(Some(0))
+        }
+
+        let mut first = None;
+
+        scan(reader, self.pattern_len(), |buf, offset| {
+            first = self.find(buf).map(|i| offset + i);
+            first.is_none()
+        })?;
+
+        Ok(first)
+    }
+
+    // 在输入流中查找所有出现的位置
+    fn find_all_in<R: Read>(&self, reader: R) -> io::Result<Vec<usize>>
+        where Self: Sized
+    {
+        let mut result = Vec::new();
+
+        // 空模式在每个位置都出现，包括结尾
+        if self.pattern_len() == 0 {
+            let mut len = 0;
+
+            scan(reader, 1, |buf, offset| {
+                len = offset + buf.len();
+                true
+            })?;
+
+            result.extend(0..len + 1);
+            return Ok(result)
+        }
+
+        scan(reader, self.pattern_len(), |buf, offset| {
+            result.extend(self.find_all(buf).into_iter().map(|i| offset + i));
+            true
+        })?;
+
+        Ok(result)
+    }
+}
+
+// 分块读取输入，每块之前保留上一块末尾的 m - 1 个字节，跨块的匹配不会遗漏
+// 保留的字节不足 m 个，同一个匹配不会被找到两次
+// 每读入一块调用 f(buf, offset)，offset 为 buf[0] 在输入中的位置，f 返回 false 时停止读取
+fn scan<R, F>(mut reader: R, m: usize, mut f: F) -> io::Result<()>
+    where R: Read, F: FnMut(&[u8], usize) -> bool
+{
+    let mut chunk = [0; CHUNK];
+    let mut buf = Vec::with_capacity(CHUNK + m);
+    let mut offset = 0;
+
+    loop {
+        let n = match reader.read(&mut chunk) {
+            Ok(0) => return Ok(()),
+            Ok(n) => n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        buf.extend_from_slice(&chunk[..n]);
+
+        if ! f(&buf, offset) {
+            return Ok(())
+        }
+
+        let drop = buf.len().saturating_sub(m - 1);
+        buf.drain(..drop);
+        offset += drop;
+    }
+}
+
+// 所有实现共用的一致性测试
+#[cfg(test)]
+fn conformance<S: SubstringSearch, F: Fn(&[u8]) -> S>(new: F) {
+    extern crate rand;
+
+    // 每次最多读取 3 个字节，检查跨块的匹配
+    struct Trickle<'a>(&'a [u8]);
+
+    impl<'a> Read for Trickle<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.0.len().min(buf.len()).min(3);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    let txt = b"abacadabrabracabracadabrabrabracad";
+
+    let search = new(b"abracadabra");
+    assert_eq!(search.pattern_len(), 11);
+    assert_eq!(search.find(txt), Some(14));
+    assert_eq!(search.find_all(txt), [14]);
+    assert_eq!(search.find(b"abracadabr"), None);
+    assert_eq!(search.find(b""), None);
+
+    let search = new(b"rab");
+    assert_eq!(search.find(txt), Some(8));
+    assert_eq!(search.find_all(txt), [8, 23, 26]);
+    assert_eq!(search.find_in(Trickle(txt)).unwrap(), Some(8));
+    assert_eq!(search.find_all_in(Trickle(txt)).unwrap(), [8, 23, 26]);
+    assert_eq!(search.find_all_in(&txt[..]).unwrap(), [8, 23, 26]);
+
+    // 重叠的匹配
+    let search = new(b"aa");
+    assert_eq!(search.find_all(b"aaaa"), [0, 1, 2]);
+    assert_eq!(search.find_all_in(Trickle(b"aaaa")).unwrap(), [0, 1, 2]);
+
+    // 空模式
+    let search = new(b"");
+    assert_eq!(search.find(b"abc"), Some(0));
+    assert_eq!(search.find_all(b"abc"), [0, 1, 2, 3]);
+    assert_eq!(search.find_in(&b"abc"[..]).unwrap(), Some(0));
+    assert_eq!(search.find_all_in(Trickle(b"abc")).unwrap(), [0, 1, 2, 3]);
+
+    // Unicode 文本按字节偏移
+    let txt = "订单：苹果手机，苹果电脑".as_bytes();
+    let search = new("苹果".as_bytes());
+    assert_eq!(search.find_all(txt), [9, 24]);
+    assert_eq!(search.find_all_in(Trickle(txt)).unwrap(), [9, 24]);
+
+    // 随机文本，跨越多个读取块，与朴素查找对比
+    let txt: Vec<u8> = (0..3 * CHUNK).map(|_| b'a' + rand::random::<u8>() % 2).collect();
+
+    for _ in 0..20 {
+        let m = 1 + rand::random::<usize>() % 8;
+        let pat: Vec<u8> = (0..m).map(|_| b'a' + rand::random::<u8>() % 2).collect();
+        let expected: Vec<usize> = (0..txt.len() - m + 1).filter(|&i| txt[i..i + m] == pat[..]).collect();
+
+        let search = new(&pat);
+        assert_eq!(search.find(&txt), expected.first().cloned());
+        assert_eq!(search.find_all(&txt), expected);
+        assert_eq!(search.find_all_in(&txt[..]).unwrap(), expected);
+    }
+
+    // 很长的一串连续重叠的匹配
+    let txt = vec![b'a'; 100_000];
+
+    for &m in [1, 2, 50].iter() {
+        let search = new(&txt[..m]);
+        let expected: Vec<usize> = (0..txt.len() - m + 1).collect();
+        assert_eq!(search.find_all(&txt), expected);
+    }
+
+    let mut txt = b"ab".repeat(50_000);
+    txt.push(b'a');
+    let search = new(b"aba");
+    assert_eq!(search.find_all(&txt), (0..50_000).map(|i| 2 * i).collect::<Vec<_>>());
+}
+
+#[test]
+fn test() {
+    use super::kmp::Kmp;
+    use super::boyer_moore::BoyerMoore;
+    use super::rabin_karp::RabinKarp;
+
+    conformance(|pat| Kmp::new(pat));
+    conformance(|pat| BoyerMoore::new(pat));
+    conformance(|pat| RabinKarp::new(pat));
+    conformance(|pat| RabinKarp::las_vegas(pat));
+}
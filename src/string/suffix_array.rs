@@ -0,0 +1,226 @@
+// 后缀数组
+// 按字符（而不是字节）划分后缀，用倍增法排序，用 Kasai 算法计算相邻后缀的最长公共前缀
+// 对外的位置和长度都以字节计算，可以直接用来切分原字符串
+use std::cmp::Ordering;
+
+pub struct SuffixArray<'a> {
+    text: &'a str,
+    // offsets[i] 为第 i 个字符的字节位置，最后一项为文本的字节长度
+    offsets: Vec<usize>,
+    // 第 i 小的后缀从第 sa[i] 个字符开始
+    sa: Vec<usize>,
+    // lcp[i] 为第 i 小和第 i - 1 小的后缀的最长公共前缀的字符数，lcp[0] = 0
+    lcp: Vec<usize>,
+}
+
+impl<'a> SuffixArray<'a> {
+    pub fn new(text: &'a str) -> Self {
+        let s: Vec<u32> = text.chars().map(|c| c as u32).collect();
+        let mut offsets: Vec<usize> = text.char_indices().map(|(i, _)| i).collect();
+        offsets.push(text.len());
+
+        let sa = build(&s);
+        let lcp = kasai(&s, &sa);
+
+        SuffixArray { text, offsets, sa, lcp }
+    }
+
+    // 后缀的数量，即文本的字符数
+    pub fn length(&self) -> usize {
+        self.sa.len()
+    }
+
+    // 第 i 小的后缀在文本中的字节位置
+    pub fn index(&self, i: usize) -> usize {
+        self.offsets[self.sa[i]]
+    }
+
+    // 第 i 小的后缀
+    pub fn select(&self, i: usize) -> &'a str {
+        &self.text[self.index(i)..]
+    }
+
+    // 第 i 小和第 i - 1 小的后缀的最长公共前缀的字节数，i 从 1 开始
+    pub fn lcp(&self, i: usize) -> usize {
+        let start = self.sa[i];
+        self.offsets[start + self.lcp[i]] - self.offsets[start]
+    }
+
+    // 小于 query 的后缀的数量
+    pub fn rank(&self, query: &str) -> usize {
+        let (mut lo, mut hi) = (0, self.length());
+
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+
+            match self.select(mid).cmp(query) {
+                Ordering::Less => lo = mid + 1,
+                _ => hi = mid,
+            }
+        }
+
+        lo
+    }
+
+    // 出现至少两次的最长子串，可以重叠
+    pub fn longest_repeated_substring(&self) -> &'a str {
+        let mut lrs = "";
+
+        for i in 1..self.length() {
+            if self.lcp(i) > lrs.len() {
+                lrs = &self.select(i)[..self.lcp(i)];
+            }
+        }
+
+        lrs
+    }
+
+    // a 和 b 的最长公共子串，返回 a 的切片
+    // 在 a 和 b 之间插入一个不是字符的分隔符，相邻的两个后缀分别来自 a 和 b 时才计入
+    pub fn longest_common_substring<'b>(a: &'b str, b: &str) -> &'b str {
+        let sep = char::MAX as u32 + 1;
+        let mut s: Vec<u32> = a.chars().map(|c| c as u32).collect();
+        let na = s.len();
+        s.push(sep);
+        s.extend(b.chars().map(|c| c as u32));
+
+        let sa = build(&s);
+        let lcp = kasai(&s, &sa);
+        let (mut start, mut len) = (0, 0);
+
+        for i in 1..sa.len() {
+            if (sa[i] < na) != (sa[i - 1] < na) && lcp[i] > len {
+                start = sa[i].min(sa[i - 1]);
+                len = lcp[i];
+            }
+        }
+
+        let mut chars = a.char_indices().map(|(i, _)| i).chain(Some(a.len()));
+        let lo = chars.nth(start).unwrap_or(0);
+        let hi = if len == 0 { lo } else { chars.nth(len - 1).unwrap() };
+
+        &a[lo..hi]
+    }
+}
+
+// 倍增法构造后缀数组
+// 第 k 轮按前 2^k 个符号的排名排序，排名互不相同时结束
+fn build(s: &[u32]) -> Vec<usize> {
+    let n = s.len();
+    let mut sa: Vec<usize> = (0..n).collect();
+    let mut rank: Vec<usize> = s.iter().map(|&c| c as usize).collect();
+    let mut tmp = vec![0; n];
+    let mut k = 1;
+
+    if n < 2 {
+        return sa
+    }
+
+    loop {
+        // 超出结尾的部分最小
+        let key = |i: usize| (rank[i], if i + k < n { rank[i + k] + 1 } else { 0 });
+        sa.sort_by_key(|&i| key(i));
+
+        tmp[sa[0]] = 0;
+
+        for i in 1..n {
+            tmp[sa[i]] = tmp[sa[i - 1]] + (key(sa[i - 1]) < key(sa[i])) as usize;
+        }
+
+        rank.copy_from_slice(&tmp);
+
+        if rank[sa[n - 1]] == n - 1 {
+            break
+        }
+
+        k *= 2;
+    }
+
+    sa
+}
+
+// Kasai 算法，线性时间计算 lcp 数组
+// 后缀 i 与它前一名的公共前缀为 h 时，后缀 i + 1 与它前一名的公共前缀至少为 h - 1
+fn kasai(s: &[u32], sa: &[usize]) -> Vec<usize> {
+    let n = s.len();
+    let mut rank = vec![0; n];
+    let mut lcp = vec![0; n];
+
+    for (i, &p) in sa.iter().enumerate() {
+        rank[p] = i;
+    }
+
+    let mut h: usize = 0;
+
+    for i in 0..n {
+        if rank[i] == 0 {
+            h = 0;
+            continue
+        }
+
+        let j = sa[rank[i] - 1];
+
+        while i + h < n && j + h < n && s[i + h] == s[j + h] {
+            h += 1;
+        }
+
+        lcp[rank[i]] = h;
+        h = h.saturating_sub(1);
+    }
+
+    lcp
+}
+
+#[test]
+fn test() {
+    extern crate rand;
+
+    let sa = SuffixArray::new("aacaagtttacaagc");
+    let suffixes: Vec<&str> = (0..sa.length()).map(|i| sa.select(i)).collect();
+    let lcps: Vec<usize> = (1..sa.length()).map(|i| sa.lcp(i)).collect();
+
+    assert_eq!(suffixes, [
+        "aacaagtttacaagc", "aagc", "aagtttacaagc", "acaagc", "acaagtttacaagc",
+        "agc", "agtttacaagc", "c", "caagc", "caagtttacaagc",
+        "gc", "gtttacaagc", "tacaagc", "ttacaagc", "tttacaagc",
+    ]);
+    assert_eq!(lcps, [2, 3, 1, 5, 1, 2, 0, 1, 4, 0, 1, 0, 1, 2]);
+    assert_eq!(sa.index(0), 0);
+    assert_eq!(sa.index(1), 11);
+
+    assert_eq!(sa.rank("aa"), 0);
+    assert_eq!(sa.rank("ab"), 3);
+    assert_eq!(sa.rank("c"), 7);
+    assert_eq!(sa.rank("z"), 15);
+
+    assert_eq!(sa.longest_repeated_substring(), "acaag");
+    assert_eq!(SuffixArray::new("banana").longest_repeated_substring(), "ana");
+    assert_eq!(SuffixArray::new("abc").longest_repeated_substring(), "");
+    assert_eq!(SuffixArray::new("").length(), 0);
+
+    // Unicode
+    let sa = SuffixArray::new("苹果手机和苹果手表");
+    assert_eq!(sa.length(), 9);
+    assert_eq!(sa.longest_repeated_substring(), "苹果手");
+    assert_eq!(sa.select(sa.rank("苹果")), "苹果手机和苹果手表");
+
+    for i in 1..sa.length() {
+        assert!(sa.select(i - 1) < sa.select(i));
+        assert_eq!(&sa.select(i)[..sa.lcp(i)], &sa.select(i - 1)[..sa.lcp(i)]);
+    }
+
+    assert_eq!(SuffixArray::longest_common_substring("苹果手机壳", "买苹果手机"), "苹果手机");
+    assert_eq!(SuffixArray::longest_common_substring("abcdxyz", "xyzabcd"), "abcd");
+    assert_eq!(SuffixArray::longest_common_substring("abc", "xyz"), "");
+    assert_eq!(SuffixArray::longest_common_substring("", "xyz"), "");
+
+    // 与直接排序所有后缀对比
+    let text: String = (0..300).map(|_| (b'a' + rand::random::<u8>() % 3) as char).collect();
+    let sa = SuffixArray::new(&text);
+    let mut expected: Vec<&str> = (0..text.len()).map(|i| &text[i..]).collect();
+    expected.sort();
+
+    for (i, suffix) in expected.iter().enumerate() {
+        assert_eq!(sa.select(i), *suffix);
+    }
+}
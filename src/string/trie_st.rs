@@ -1,84 +1,216 @@
 // 字典树
+// 按 UTF-8 字节建树，任意 Unicode 键都可以使用，每个字符占 1 到 4 层
+// 子结点按字节有序稀疏存储，结点只为实际存在的分支分配空间
 use std::fmt;
-const R: usize = 256;
-
-type Link<T> = Option<Box<Node<T>>>;
 
 #[derive(Debug)]
 struct Node<T> {
     val: Option<T>,
-    next: Vec<Link<T>>
-}
-
-trait LinkMethods<T> {
-    fn new() -> Link<T>;
-    fn get(&self, key: &str, d: usize) -> &Link<T>;
-    fn put(&mut self, key: &str, val: T, d: usize) -> Link<T>;
+    next: Vec<(u8, Box<Node<T>>)>,
 }
 
-impl<T: fmt::Debug> LinkMethods<T> for Link<T> {
+impl<T> Node<T> {
     fn new() -> Self {
-        let mut this = Box::new(Node {
-            val: None,
-            next: Vec::with_capacity(R),
-        });
+        Node { val: None, next: Vec::new() }
+    }
 
-        for _ in 0..R {
-            this.next.push(None);
+    fn child(&self, c: u8) -> Option<&Node<T>> {
+        match self.next.binary_search_by_key(&c, |&(b, _)| b) {
+            Ok(i) => Some(&self.next[i].1),
+            Err(_) => None,
         }
-
-        Some(this)
     }
 
-    fn get(&self, key: &str, d: usize) -> &Self {
-        match *self {
-            None => &self,
-            Some(_) if d == key.chars().count() => &self,
-            Some(ref boxed_node) => {
-                let c = key.chars().nth(d).unwrap() as usize;
-                boxed_node.next[c].get(key, d + 1)
-            }
+    fn child_mut(&mut self, c: u8) -> Option<&mut Node<T>> {
+        match self.next.binary_search_by_key(&c, |&(b, _)| b) {
+            Ok(i) => Some(&mut self.next[i].1),
+            Err(_) => None,
         }
     }
 
-    fn put(&mut self, key: &str, val: T, d: usize) -> Link<T> {
-        let mut x = match self.take() {
-            Some(mut boxed_node) => boxed_node,
-            None => Self::new().unwrap(),
+    // 子结点不存在时插入
+    fn child_or_insert(&mut self, c: u8) -> &mut Node<T> {
+        let i = match self.next.binary_search_by_key(&c, |&(b, _)| b) {
+            Ok(i) => i,
+            Err(i) => {
+                self.next.insert(i, (c, Box::new(Node::new())));
+                i
+            },
+        };
+
+        &mut self.next[i].1
+    }
+
+    // 删除 key 对应的值，返回被删除的值
+    // 没有值也没有子结点的结点一并删除
+    fn delete(&mut self, key: &[u8]) -> Option<T> {
+        let c = match key.first() {
+            Some(&c) => c,
+            None => return self.val.take(),
+        };
+
+        let i = match self.next.binary_search_by_key(&c, |&(b, _)| b) {
+            Ok(i) => i,
+            Err(_) => return None,
         };
 
-        if d == key.chars().count() {
-            x.val = Some(val);
+        let val = self.next[i].1.delete(&key[1..]);
+
+        if self.next[i].1.is_empty() {
+            self.next.remove(i);
+        }
+
+        val
+    }
+
+    fn is_empty(&self) -> bool {
+        self.val.is_none() && self.next.is_empty()
+    }
+
+    fn collect(&self, pre: &mut Vec<u8>, q: &mut Vec<String>) {
+        if self.val.is_some() {
+            q.push(to_string(pre));
+        }
+
+        for &(c, ref node) in self.next.iter() {
+            pre.push(c);
+            node.collect(pre, q);
+            pre.pop();
+        }
+    }
+
+    // 模式中的 '.' 匹配任意一个字符
+    fn collect_match(&self, pre: &mut Vec<u8>, pat: &[char], q: &mut Vec<String>) {
+        match pat.first() {
+            None => {
+                if self.val.is_some() {
+                    q.push(to_string(pre));
+                }
+            },
+            Some(&'.') => {
+                for &(c, ref node) in self.next.iter() {
+                    pre.push(c);
+                    node.collect_char(pre, utf8_width(c) - 1, &pat[1..], q);
+                    pre.pop();
+                }
+            },
+            Some(&c) => {
+                let mut buf = [0; 4];
+                let mut x = Some(self);
+
+                for &b in c.encode_utf8(&mut buf).as_bytes() {
+                    x = x.and_then(|node| node.child(b));
+                }
+
+                if let Some(node) = x {
+                    let len = pre.len();
+                    pre.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                    node.collect_match(pre, &pat[1..], q);
+                    pre.truncate(len);
+                }
+            },
         }
-        else {
-            let c = key.chars().nth(d).unwrap() as usize;
-            x.next[c] = x.next[c].put(key, val, d + 1);
+    }
+
+    // 沿着所有分支走完当前字符剩余的 rest 个字节，再继续匹配模式
+    fn collect_char(&self, pre: &mut Vec<u8>, rest: usize, pat: &[char], q: &mut Vec<String>) {
+        if rest == 0 {
+            return self.collect_match(pre, pat, q)
         }
 
-        Some(x)
+        for &(c, ref node) in self.next.iter() {
+            pre.push(c);
+            node.collect_char(pre, rest - 1, pat, q);
+            pre.pop();
+        }
     }
 }
 
+// 根据 UTF-8 首字节得到字符占用的字节数
+fn utf8_width(c: u8) -> usize {
+    match c {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        _ => 4,
+    }
+}
+
+// 树中的键都来自合法的 &str，路径上的字节一定是合法的 UTF-8
+fn to_string(bytes: &[u8]) -> String {
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
 #[derive(Debug)]
 pub struct TrieST<T> {
-    root: Link<T>,
+    root: Node<T>,
+    n: usize,
 }
 
 impl<T: fmt::Debug> TrieST<T> {
 
     pub fn new() -> Self {
-        TrieST { root: None }
+        TrieST { root: Node::new(), n: 0 }
+    }
+
+    fn node(&self, key: &str) -> Option<&Node<T>> {
+        let mut x = Some(&self.root);
+
+        for &c in key.as_bytes() {
+            x = x.and_then(|node| node.child(c));
+        }
+
+        x
     }
 
     pub fn get(&self, key: &str) -> &Option<T> {
-        match *self.root.get(key, 0) {
-            Some(ref boxed_node) => &boxed_node.val,
+        match self.node(key) {
+            Some(node) => &node.val,
             None => &None,
         }
     }
 
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut T> {
+        let mut x = Some(&mut self.root);
+
+        for &c in key.as_bytes() {
+            x = x.and_then(|node| node.child_mut(c));
+        }
+
+        x.and_then(|node| node.val.as_mut())
+    }
+
     pub fn put(&mut self, key: &str, val: T) {
-        self.root = self.root.put(key, val, 0);
+        let mut x = &mut self.root;
+
+        for &c in key.as_bytes() {
+            x = x.child_or_insert(c);
+        }
+
+        if x.val.is_none() {
+            self.n += 1;
+        }
+
+        x.val = Some(val);
+    }
+
+    pub fn delete(&mut self, key: &str) {
+        if self.root.delete(key.as_bytes()).is_some() {
+            self.n -= 1;
+        }
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    // 键的数量
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
     }
 
     // 查找所有键
@@ -88,32 +220,49 @@ impl<T: fmt::Debug> TrieST<T> {
 
     // 前缀匹配
     pub fn keys_with_prefix(&self, pre: &str) -> Vec<String> {
-        let pre = String::from(pre);
         let mut q = Vec::new();
-        self.collect(self.root.get(pre.as_ref(), 0), pre, &mut q);
+
+        if let Some(node) = self.node(pre) {
+            node.collect(&mut pre.as_bytes().to_vec(), &mut q);
+        }
+
         q
     }
 
-    fn collect(&self, node: &Link<T>, pre: String, q: &mut Vec<String>) {
-        let boxed_node = match *node {
-            Some(ref boxed_node) => boxed_node,
-            None => return,
-        };
+    // 通配符匹配，'.' 匹配任意一个字符
+    pub fn keys_that_match(&self, pat: &str) -> Vec<String> {
+        let pat: Vec<char> = pat.chars().collect();
+        let mut q = Vec::new();
+        self.root.collect_match(&mut Vec::new(), &pat, &mut q);
+        q
+    }
+
+    // query 的前缀中最长的键
+    // 键是合法的 UTF-8，与 query 字节前缀相同时一定落在字符边界上
+    pub fn longest_prefix_of<'a>(&self, query: &'a str) -> Option<&'a str> {
+        let mut x = Some(&self.root);
+        let mut length = None;
 
-        if boxed_node.val.is_some() {
-            q.push(pre.clone());
+        for (d, &c) in query.as_bytes().iter().enumerate() {
+            let node = match x {
+                Some(node) => node,
+                None => break,
+            };
+
+            if node.val.is_some() {
+                length = Some(d);
+            }
+
+            x = node.child(c);
         }
 
-        for c in 0..R {
-            match String::from_utf8(vec![c as u8]) {
-                Ok(cc) => {
-                    let mut pre = pre.clone();
-                    pre.push_str(cc.as_ref());
-                    self.collect(&boxed_node.next[c], pre, q);
-                },
-                Err(_) => {},
+        if let Some(node) = x {
+            if node.val.is_some() {
+                length = Some(query.len());
             }
         }
+
+        length.map(|length| &query[..length])
     }
 }
 
@@ -131,4 +280,74 @@ fn test() {
     assert_eq!(trie_st.get("def"), &Some(4));
     assert_eq!(trie_st.keys(), ["abc", "abf", "bde", "cbd", "def"]);
     assert_eq!(trie_st.keys_with_prefix("ab"), ["abc", "abf"]);
-}
\ No newline at end of file
+
+    assert_eq!(trie_st.size(), 5);
+    assert!(trie_st.contains("abc"));
+    assert!(! trie_st.contains("ab"));
+
+    *trie_st.get_mut("abc").unwrap() += 10;
+    assert_eq!(trie_st.get("abc"), &Some(11));
+    assert_eq!(trie_st.get_mut("ab"), None);
+
+    // 更新值不改变数量
+    trie_st.put("abc", 1);
+    assert_eq!(trie_st.size(), 5);
+
+    assert_eq!(trie_st.keys_that_match("ab."), ["abc", "abf"]);
+    assert_eq!(trie_st.keys_that_match(".b."), ["abc", "abf", "cbd"]);
+    assert_eq!(trie_st.keys_that_match("..."), trie_st.keys());
+    assert!(trie_st.keys_that_match("a.").is_empty());
+
+    trie_st.put("a", 6);
+    trie_st.put("ab", 7);
+    assert_eq!(trie_st.longest_prefix_of("abcd"), Some("abc"));
+    assert_eq!(trie_st.longest_prefix_of("abd"), Some("ab"));
+    assert_eq!(trie_st.longest_prefix_of("ax"), Some("a"));
+    assert_eq!(trie_st.longest_prefix_of("x"), None);
+    assert_eq!(trie_st.longest_prefix_of(""), None);
+
+    // 删除 abc 后，c 结点被剪掉，ab 仍然保留
+    trie_st.delete("abc");
+    trie_st.delete("xyz");
+    assert_eq!(trie_st.size(), 6);
+    assert!(! trie_st.contains("abc"));
+    assert_eq!(trie_st.keys_with_prefix("ab"), ["ab", "abf"]);
+
+    trie_st.delete("abf");
+    trie_st.delete("ab");
+    assert!(trie_st.node("ab").is_none());
+    assert_eq!(trie_st.keys_with_prefix("a"), ["a"]);
+
+    for key in trie_st.keys() {
+        trie_st.delete(&key);
+    }
+
+    assert!(trie_st.is_empty());
+    assert!(trie_st.root.next.is_empty());
+
+    // Unicode 键
+    let mut trie_st = TrieST::new();
+
+    trie_st.put("苹果手机", 1);
+    trie_st.put("苹果", 2);
+    trie_st.put("苹果电脑", 3);
+    trie_st.put("香蕉", 4);
+    trie_st.put("café", 5);
+    trie_st.put("😀", 6);
+
+    assert_eq!(trie_st.size(), 6);
+    assert_eq!(trie_st.get("苹果电脑"), &Some(3));
+    assert_eq!(trie_st.get("苹"), &None);
+    assert_eq!(trie_st.keys(), ["café", "苹果", "苹果手机", "苹果电脑", "香蕉", "😀"]);
+    assert_eq!(trie_st.keys_with_prefix("苹果"), ["苹果", "苹果手机", "苹果电脑"]);
+    assert_eq!(trie_st.keys_that_match("苹果.脑"), ["苹果电脑"]);
+    assert_eq!(trie_st.keys_that_match(".."), ["苹果", "香蕉"]);
+    assert_eq!(trie_st.keys_that_match("caf."), ["café"]);
+    assert_eq!(trie_st.keys_that_match("."), ["😀"]);
+    assert_eq!(trie_st.longest_prefix_of("苹果手表"), Some("苹果"));
+    assert_eq!(trie_st.longest_prefix_of("苹果手机壳"), Some("苹果手机"));
+
+    trie_st.delete("苹果");
+    assert_eq!(trie_st.keys_with_prefix("苹"), ["苹果手机", "苹果电脑"]);
+    assert_eq!(trie_st.longest_prefix_of("苹果手表"), None);
+}
@@ -0,0 +1,499 @@
+// 三向单词查找树
+// 每个结点保存一个字符和左、中、右三个链接，只为实际出现的字符分配结点
+use std::fmt;
+
+type Link<V> = Option<Box<Node<V>>>;
+
+#[derive(Debug)]
+struct Node<V> {
+    c: char,
+    val: Option<V>,
+    left: Link<V>,
+    mid: Link<V>,
+    right: Link<V>,
+}
+
+trait LinkMethods<V> {
+    fn new(c: char) -> Self;
+    fn get(&self, key: &[char]) -> Option<&Node<V>>;
+    fn get_mut(&mut self, key: &[char]) -> Option<&mut Node<V>>;
+    fn put(&mut self, key: &[char], val: V) -> Option<V>;
+    fn delete(&mut self, key: &[char]) -> Option<V>;
+    fn delete_min(&mut self) -> Link<V>;
+    fn collect(&self, pre: &mut String, q: &mut Vec<String>);
+    fn collect_match(&self, pre: &mut String, pat: &[char], q: &mut Vec<String>);
+}
+
+impl<V> LinkMethods<V> for Link<V> {
+    fn new(c: char) -> Self {
+        Some(Box::new(Node {
+            c,
+            val: None,
+            left: None,
+            mid: None,
+            right: None,
+        }))
+    }
+
+    // key 不能为空
+    fn get(&self, key: &[char]) -> Option<&Node<V>> {
+        let mut x = self;
+        let mut d = 0;
+
+        while let Some(ref node) = *x {
+            if key[d] < node.c {
+                x = &node.left;
+            }
+            else if key[d] > node.c {
+                x = &node.right;
+            }
+            else if d < key.len() - 1 {
+                x = &node.mid;
+                d += 1;
+            }
+            else {
+                return Some(node)
+            }
+        }
+
+        None
+    }
+
+    fn get_mut(&mut self, key: &[char]) -> Option<&mut Node<V>> {
+        let node = match *self {
+            Some(ref mut node) => node,
+            None => return None,
+        };
+
+        if key[0] < node.c {
+            node.left.get_mut(key)
+        }
+        else if key[0] > node.c {
+            node.right.get_mut(key)
+        }
+        else if key.len() > 1 {
+            node.mid.get_mut(&key[1..])
+        }
+        else {
+            Some(node)
+        }
+    }
+
+    // 返回键原来对应的值
+    fn put(&mut self, key: &[char], val: V) -> Option<V> {
+        if self.is_none() {
+            *self = Self::new(key[0]);
+        }
+
+        let node = self.as_mut().unwrap();
+
+        if key[0] < node.c {
+            node.left.put(key, val)
+        }
+        else if key[0] > node.c {
+            node.right.put(key, val)
+        }
+        else if key.len() > 1 {
+            node.mid.put(&key[1..], val)
+        }
+        else {
+            node.val.replace(val)
+        }
+    }
+
+    // 返回被删除的值
+    // 没有值也没有中子结点的结点被删除，左右子树按二分搜索树的方式接上
+    fn delete(&mut self, key: &[char]) -> Option<V> {
+        let val = {
+            let node = match *self {
+                Some(ref mut node) => node,
+                None => return None,
+            };
+
+            if key[0] < node.c {
+                node.left.delete(key)
+            }
+            else if key[0] > node.c {
+                node.right.delete(key)
+            }
+            else if key.len() > 1 {
+                node.mid.delete(&key[1..])
+            }
+            else {
+                node.val.take()
+            }
+        };
+
+        let prune = match *self {
+            Some(ref node) => node.val.is_none() && node.mid.is_none(),
+            None => false,
+        };
+
+        if prune {
+            let mut node = self.take().unwrap();
+
+            *self = match (node.left.take(), node.right.take()) {
+                (None, right) => right,
+                (left, None) => left,
+                (left, mut right) => {
+                    let mut min = right.delete_min();
+
+                    {
+                        let min = min.as_mut().unwrap();
+                        min.left = left;
+                        min.right = right;
+                    }
+
+                    min
+                },
+            };
+        }
+
+        val
+    }
+
+    // 从左右链接组成的二分搜索树中取出字符最小的结点
+    fn delete_min(&mut self) -> Link<V> {
+        if self.as_ref().unwrap().left.is_some() {
+            self.as_mut().unwrap().left.delete_min()
+        }
+        else {
+            let mut node = self.take().unwrap();
+            *self = node.right.take();
+            Some(node)
+        }
+    }
+
+    fn collect(&self, pre: &mut String, q: &mut Vec<String>) {
+        let node = match *self {
+            Some(ref node) => node,
+            None => return,
+        };
+
+        node.left.collect(pre, q);
+
+        pre.push(node.c);
+
+        if node.val.is_some() {
+            q.push(pre.clone());
+        }
+
+        node.mid.collect(pre, q);
+        pre.pop();
+
+        node.right.collect(pre, q);
+    }
+
+    // 模式中的 '.' 匹配任意一个字符，pat 不能为空
+    fn collect_match(&self, pre: &mut String, pat: &[char], q: &mut Vec<String>) {
+        let node = match *self {
+            Some(ref node) => node,
+            None => return,
+        };
+
+        let c = pat[0];
+
+        if c == '.' || c < node.c {
+            node.left.collect_match(pre, pat, q);
+        }
+
+        if c == '.' || c == node.c {
+            pre.push(node.c);
+
+            if pat.len() == 1 {
+                if node.val.is_some() {
+                    q.push(pre.clone());
+                }
+            }
+            else {
+                node.mid.collect_match(pre, &pat[1..], q);
+            }
+
+            pre.pop();
+        }
+
+        if c == '.' || c > node.c {
+            node.right.collect_match(pre, pat, q);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TST<V> {
+    root: Link<V>,
+    // 空字符串没有对应的结点，单独保存
+    empty: Option<V>,
+    n: usize,
+}
+
+// 空的树不需要 V: Default
+impl<V> Default for TST<V> {
+    fn default() -> Self {
+        TST { root: None, empty: None, n: 0 }
+    }
+}
+
+impl<V: fmt::Debug> TST<V> {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, key: &str) -> &Option<V> {
+        if key.is_empty() {
+            return &self.empty
+        }
+
+        let key: Vec<char> = key.chars().collect();
+
+        match self.root.get(&key) {
+            Some(node) => &node.val,
+            None => &None,
+        }
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+        if key.is_empty() {
+            return self.empty.as_mut()
+        }
+
+        let key: Vec<char> = key.chars().collect();
+        self.root.get_mut(&key).and_then(|node| node.val.as_mut())
+    }
+
+    pub fn put(&mut self, key: &str, val: V) {
+        let old = if key.is_empty() {
+            self.empty.replace(val)
+        }
+        else {
+            let key: Vec<char> = key.chars().collect();
+            self.root.put(&key, val)
+        };
+
+        if old.is_none() {
+            self.n += 1;
+        }
+    }
+
+    pub fn delete(&mut self, key: &str) {
+        let old = if key.is_empty() {
+            self.empty.take()
+        }
+        else {
+            let key: Vec<char> = key.chars().collect();
+            self.root.delete(&key)
+        };
+
+        if old.is_some() {
+            self.n -= 1;
+        }
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    // 键的数量
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    // 查找所有键
+    pub fn keys(&self) -> Vec<String> {
+        self.keys_with_prefix("")
+    }
+
+    // 前缀匹配
+    pub fn keys_with_prefix(&self, pre: &str) -> Vec<String> {
+        let mut q = Vec::new();
+
+        if pre.is_empty() {
+            if self.empty.is_some() {
+                q.push(String::new());
+            }
+
+            self.root.collect(&mut String::new(), &mut q);
+            return q
+        }
+
+        let key: Vec<char> = pre.chars().collect();
+
+        if let Some(node) = self.root.get(&key) {
+            if node.val.is_some() {
+                q.push(String::from(pre));
+            }
+
+            node.mid.collect(&mut String::from(pre), &mut q);
+        }
+
+        q
+    }
+
+    // 通配符匹配，'.' 匹配任意一个字符
+    pub fn keys_that_match(&self, pat: &str) -> Vec<String> {
+        let mut q = Vec::new();
+
+        if pat.is_empty() {
+            if self.empty.is_some() {
+                q.push(String::new());
+            }
+
+            return q
+        }
+
+        let pat: Vec<char> = pat.chars().collect();
+        self.root.collect_match(&mut String::new(), &pat, &mut q);
+        q
+    }
+
+    // query 的前缀中最长的键
+    pub fn longest_prefix_of<'a>(&self, query: &'a str) -> Option<&'a str> {
+        let mut length = if self.empty.is_some() { Some(0) } else { None };
+        let mut x = &self.root;
+
+        for (i, c) in query.char_indices() {
+            // 在左右链接组成的二分搜索树中查找字符 c
+            let mut found = None;
+
+            while let Some(ref node) = *x {
+                if c < node.c {
+                    x = &node.left;
+                }
+                else if c > node.c {
+                    x = &node.right;
+                }
+                else {
+                    found = Some(node);
+                    break
+                }
+            }
+
+            let node = match found {
+                Some(node) => node,
+                None => break,
+            };
+
+            if node.val.is_some() {
+                length = Some(i + c.len_utf8());
+            }
+
+            x = &node.mid;
+        }
+
+        length.map(|length| &query[..length])
+    }
+}
+
+
+#[test]
+fn test() {
+    extern crate rand;
+    use std::collections::BTreeMap;
+
+    // 值的类型不需要实现 Default
+    #[derive(Debug, PartialEq)]
+    struct NoDefault(u8);
+
+    let mut tst: TST<NoDefault> = TST::default();
+    tst.put("a", NoDefault(1));
+    assert_eq!(tst.get("a"), &Some(NoDefault(1)));
+
+    let mut tst = TST::new();
+
+    tst.put("abc", 1);
+    tst.put("cbd", 2);
+    tst.put("bde", 3);
+    tst.put("def", 4);
+    tst.put("abf", 5);
+
+    assert_eq!(tst.get("def"), &Some(4));
+    assert_eq!(tst.get("de"), &None);
+    assert_eq!(tst.keys(), ["abc", "abf", "bde", "cbd", "def"]);
+    assert_eq!(tst.keys_with_prefix("ab"), ["abc", "abf"]);
+
+    assert_eq!(tst.size(), 5);
+    assert!(tst.contains("abc"));
+    assert!(! tst.contains("ab"));
+
+    *tst.get_mut("abc").unwrap() += 10;
+    assert_eq!(tst.get("abc"), &Some(11));
+    assert_eq!(tst.get_mut("ab"), None);
+
+    // 更新值不改变数量
+    tst.put("abc", 1);
+    assert_eq!(tst.size(), 5);
+
+    assert_eq!(tst.keys_that_match("ab."), ["abc", "abf"]);
+    assert_eq!(tst.keys_that_match(".b."), ["abc", "abf", "cbd"]);
+    assert_eq!(tst.keys_that_match("..."), tst.keys());
+    assert!(tst.keys_that_match("a.").is_empty());
+
+    tst.put("a", 6);
+    tst.put("ab", 7);
+    assert_eq!(tst.longest_prefix_of("abcd"), Some("abc"));
+    assert_eq!(tst.longest_prefix_of("abd"), Some("ab"));
+    assert_eq!(tst.longest_prefix_of("ax"), Some("a"));
+    assert_eq!(tst.longest_prefix_of("x"), None);
+    assert_eq!(tst.longest_prefix_of(""), None);
+
+    tst.delete("abc");
+    tst.delete("xyz");
+    assert_eq!(tst.size(), 6);
+    assert!(! tst.contains("abc"));
+    assert_eq!(tst.keys_with_prefix("ab"), ["ab", "abf"]);
+
+    // 空字符串
+    tst.put("", 0);
+    assert_eq!(tst.get(""), &Some(0));
+    assert_eq!(tst.keys()[0], "");
+    assert_eq!(tst.keys_that_match(""), [""]);
+    assert_eq!(tst.longest_prefix_of("x"), Some(""));
+
+    for key in tst.keys() {
+        tst.delete(&key);
+    }
+
+    assert!(tst.is_empty());
+    assert!(tst.root.is_none());
+
+    // Unicode 键
+    tst.put("苹果手机", 1);
+    tst.put("苹果", 2);
+    tst.put("苹果电脑", 3);
+    tst.put("香蕉", 4);
+    tst.put("café", 5);
+
+    assert_eq!(tst.keys(), ["café", "苹果", "苹果手机", "苹果电脑", "香蕉"]);
+    assert_eq!(tst.keys_with_prefix("苹果"), ["苹果", "苹果手机", "苹果电脑"]);
+    assert_eq!(tst.keys_that_match("苹果.脑"), ["苹果电脑"]);
+    assert_eq!(tst.keys_that_match(".."), ["苹果", "香蕉"]);
+    assert_eq!(tst.longest_prefix_of("苹果手机壳"), Some("苹果手机"));
+
+    // 与 BTreeMap 对比，随机插入和删除
+    let mut tst = TST::new();
+    let mut expected = BTreeMap::new();
+
+    for _ in 0..2000 {
+        let len = rand::random::<usize>() % 4;
+        let key: String = (0..len).map(|_| (b'a' + rand::random::<u8>() % 4) as char).collect();
+
+        if rand::random::<bool>() {
+            tst.put(&key, len);
+            expected.insert(key, len);
+        }
+        else {
+            tst.delete(&key);
+            expected.remove(&key);
+        }
+
+        assert_eq!(tst.size(), expected.len());
+    }
+
+    let keys: Vec<String> = expected.keys().cloned().collect();
+    assert_eq!(tst.keys(), keys);
+    assert_eq!(tst.keys_with_prefix("ab"), keys.iter().filter(|k| k.starts_with("ab")).cloned().collect::<Vec<_>>());
+}
@@ -1,20 +1,50 @@
+use std::mem;
 use std::cmp::Ordering;
+use std::iter::FromIterator;
+use std::ops::RangeBounds;
+use super::ordered_map::OrderedMap;
+use super::iter::{self, TreeNode};
 
-type Link<K, V> = Option<Box<Node<K, V>>>;
+pub type Link<K, V> = Option<Box<Node<K, V>>>;
 
 #[derive(Debug)]
-struct Node<K, V> {
-    key: K,
-    val: V,
+pub struct Node<K, V> {
+    pub key: K,
+    pub val: V,
     n: usize,
     h: isize,
     left: Link<K, V>,
     right: Link<K, V>,
 }
 
+pub type Iter<'a, K, V> = iter::Iter<'a, K, V, Node<K, V>>;
+pub type IterMut<'a, K, V> = iter::IterMut<'a, K, V, Node<K, V>>;
+pub type IntoIter<K, V> = iter::IntoIter<K, V, Node<K, V>>;
+pub type Keys<'a, K, V> = iter::Keys<'a, K, V, Node<K, V>>;
+pub type Values<'a, K, V> = iter::Values<'a, K, V, Node<K, V>>;
+pub type Range<'a, K, V, R> = iter::Range<'a, K, V, Node<K, V>, R>;
+
+impl<K, V> TreeNode<K, V> for Node<K, V> {
+    fn parts(&self) -> (&K, &V, Option<&Self>, Option<&Self>) {
+        (&self.key, &self.val, self.left.as_deref(), self.right.as_deref())
+    }
+
+    fn parts_mut(&mut self) -> (&K, &mut V, Option<&mut Self>, Option<&mut Self>) {
+        (&self.key, &mut self.val, self.left.as_deref_mut(), self.right.as_deref_mut())
+    }
+
+    fn into_parts(self) -> (K, V, Option<Box<Self>>, Option<Box<Self>>) {
+        (self.key, self.val, self.left, self.right)
+    }
+}
+
 trait LinkMethods<K, V> {
     fn new(key: K, val: V) -> Link<K, V>;
     fn put(&mut self, key: K, val: V);
+    fn get(&self, key: &K) -> Option<&V>;
+    fn delete(&mut self, key: &K);
+    fn delete_min(&mut self);
+    fn delete_max(&mut self);
     fn size(&self) -> usize;
     fn height(&self) -> isize;
     fn update_size(&mut self);
@@ -27,7 +57,19 @@ trait LinkMethods<K, V> {
     fn rotate_left(&mut self);
     fn rotate_right(&mut self);
     fn balance(&mut self);
+    fn balance_factor(&self) -> isize;
     fn is_balance(&self) -> bool;
+    fn min(&self) -> &Link<K, V>;
+    fn min_mut(&mut self) -> &mut Link<K, V>;
+    fn max(&self) -> &Link<K, V>;
+    fn select(&self, k: usize) -> &Link<K, V>;
+    fn rank(&self, key: &K) -> usize;
+    fn floor(&self, key: &K) -> &Link<K, V>;
+    fn ceiling(&self, key: &K) -> &Link<K, V>;
+    fn pre_order(&self) -> Vec<&Node<K, V>>;
+    fn in_order(&self) -> Vec<&Node<K, V>>;
+    fn post_order(&self) -> Vec<&Node<K, V>>;
+    fn level_order(&self) -> Vec<&Node<K, V>>;
 }
 
 impl<K : PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
@@ -57,6 +99,79 @@ impl<K : PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
         self.balance();
     }
 
+    fn get(&self, key: &K) -> Option<&V> {
+        match Self::compare_key(key, self) {
+            Some(Ordering::Less) => self.left().get(key),
+            Some(Ordering::Greater) => self.right().get(key),
+            Some(Ordering::Equal) => Some(&self.as_ref().unwrap().val),
+            None => None,
+        }
+    }
+
+    fn delete(&mut self, key: &K) {
+        match Self::compare_key(key, self) {
+            Some(Ordering::Less) => self.left_mut().delete(key),
+            Some(Ordering::Greater) => self.right_mut().delete(key),
+            Some(Ordering::Equal) => {
+                let mut boxed_node = self.take().unwrap();
+
+                match (boxed_node.left.take(), boxed_node.right.take()) {
+                    (None, None) => {},
+                    (leaf @ Some(_), None) | (None, leaf @ Some(_)) => *self = leaf,
+                    (left, right) => {
+                        boxed_node.left = left;
+                        boxed_node.right = right;
+
+                        // 用右子树的最小节点替换当前节点，再删除右子树的最小节点
+                        {
+                            let node = &mut *boxed_node;
+                            let next = node.right.min_mut();
+                            mem::swap(&mut node.key, &mut next.as_mut().unwrap().key);
+                            mem::swap(&mut node.val, &mut next.as_mut().unwrap().val);
+                        }
+
+                        boxed_node.right.delete_min();
+
+                        *self = Some(boxed_node);
+                    },
+                }
+            },
+            None => {},
+        }
+
+        self.balance();
+    }
+
+    fn delete_min(&mut self) {
+        if self.is_none() {
+            return
+        }
+
+        if self.left().is_none() {
+            *self = self.take().unwrap().right;
+        }
+        else {
+            self.left_mut().delete_min();
+        }
+
+        self.balance();
+    }
+
+    fn delete_max(&mut self) {
+        if self.is_none() {
+            return
+        }
+
+        if self.right().is_none() {
+            *self = self.take().unwrap().left;
+        }
+        else {
+            self.right_mut().delete_max();
+        }
+
+        self.balance();
+    }
+
     fn size(&self) -> usize {
         match *self {
             Some(ref boxed_node) => boxed_node.n,
@@ -123,11 +238,6 @@ impl<K : PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
         let mut h = self.take();
         let mut x = h.right_mut().take();
 
-        x.as_mut().map(|node| {
-            node.n = h.size();
-            node.h = h.height();
-        });
-
         h.as_mut().map(|node| {
             node.right = x.left_mut().take();
         });
@@ -135,6 +245,8 @@ impl<K : PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
         h.update_height();
 
         x.as_mut().map(|node| node.left = h);
+        x.update_size();
+        x.update_height();
 
         *self = x;
     }
@@ -143,11 +255,6 @@ impl<K : PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
         let mut h = self.take();
         let mut x = h.left_mut().take();
 
-        x.as_mut().map(|node| {
-            node.n = h.size();
-            node.h = h.height();
-        });
-
         h.as_mut().map(|node| {
             node.left = x.right_mut().take();
         });
@@ -155,42 +262,244 @@ impl<K : PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
         h.update_height();
 
         x.as_mut().map(|node| node.right = h);
+        x.update_size();
+        x.update_height();
 
         *self = x;
     }
 
+    // 插入和删除都是自底向上调用，子树已经平衡，只需要调整当前节点
     fn balance(&mut self) {
-        if self.is_some() {
-            let diff = self.left().height() - self.right().height();
+        if self.is_none() {
+            return
+        }
+
+        self.update_size();
+        self.update_height();
 
-            if diff < 2 && diff > -2 {
-                // 1 0 -1
-                self.left_mut().balance();
-                self.right_mut().balance();
+        let diff = self.balance_factor();
+
+        if diff > 1 {
+            // 左右型，先对左子树左旋
+            if self.left().balance_factor() < 0 {
+                self.left_mut().rotate_left();
             }
-            else if diff > 1 {
-                if self.left().left().height() - self.left().right().height() == -1 {
-                    self.left_mut().rotate_left();
-                }
-                self.rotate_right();
-                self.balance();
+            self.rotate_right();
+        }
+        else if diff < -1 {
+            // 右左型，先对右子树右旋
+            if self.right().balance_factor() > 0 {
+                self.right_mut().rotate_right();
             }
-            else if diff < -1 {
-                if self.right().left().height() - self.right().right().height() == 1 {
-                    self.right_mut().rotate_right();
+            self.rotate_left();
+        }
+    }
+
+    // 平衡因子，左子树高度减去右子树高度
+    fn balance_factor(&self) -> isize {
+        match *self {
+            Some(ref node) => node.left.height() - node.right.height(),
+            None => 0,
+        }
+    }
+
+    // 检查所有节点的平衡因子
+    fn is_balance(&self) -> bool {
+        match *self {
+            Some(ref node) => {
+                let diff = self.balance_factor();
+                diff < 2 && diff > -2 && node.left.is_balance() && node.right.is_balance()
+            },
+            None => true,
+        }
+    }
+
+    fn min(&self) -> &Self {
+        match *self {
+            Some(ref node) if node.left.is_some() => node.left.min(),
+            _ => self,
+        }
+    }
+
+    fn min_mut(&mut self) -> &mut Self {
+        let has_left = match *self {
+            Some(ref node) => node.left.is_some(),
+            None => false,
+        };
+
+        if has_left {
+            self.left_mut().min_mut()
+        }
+        else {
+            self
+        }
+    }
+
+    fn max(&self) -> &Self {
+        match *self {
+            Some(ref node) if node.right.is_some() => node.right.max(),
+            _ => self,
+        }
+    }
+
+    fn select(&self, k: usize) -> &Self {
+        match *self {
+            Some(ref node) => {
+                let t = node.left.size();
+
+                if k < t {
+                    node.left.select(k)
+                }
+                else if k > t {
+                    node.right.select(k - t - 1)
+                }
+                else {
+                    self
+                }
+            },
+            None => self,
+        }
+    }
+
+    fn rank(&self, key: &K) -> usize {
+        match Self::compare_key(key, self) {
+            Some(Ordering::Less) => self.left().rank(key),
+            Some(Ordering::Greater) => self.left().size() + self.right().rank(key) + 1,
+            Some(Ordering::Equal) => self.left().size(),
+            None => 0,
+        }
+    }
+
+    // 小于等于 key 的最大节点
+    fn floor(&self, key: &K) -> &Self {
+        match Self::compare_key(key, self) {
+            Some(Ordering::Less) => self.left().floor(key),
+            Some(Ordering::Greater) => {
+                let node = self.right().floor(key);
+
+                if node.is_none() {
+                    self
+                }
+                else {
+                    node
+                }
+            },
+            Some(Ordering::Equal) | None => self,
+        }
+    }
+
+    // 大于等于 key 的最小节点
+    fn ceiling(&self, key: &K) -> &Self {
+        match Self::compare_key(key, self) {
+            Some(Ordering::Less) => {
+                let node = self.left().ceiling(key);
+
+                if node.is_none() {
+                    self
+                }
+                else {
+                    node
                 }
-                self.rotate_left();
-                self.balance();
+            },
+            Some(Ordering::Greater) => self.right().ceiling(key),
+            Some(Ordering::Equal) | None => self,
+        }
+    }
+
+
+    // 前序遍历
+    fn pre_order(&self) -> Vec<&Node<K, V>> {
+        let mut stack : Vec<&Node<K, V>> = Vec::new();
+        let mut res : Vec<&Node<K, V>> = Vec::new();
+
+        if let Some(ref node) = *self {
+            stack.push(node);
+        }
+
+        while let Some(node) = stack.pop() {
+            res.push(node);
+
+            if let Some(ref right) = node.right {
+                stack.push(right);
             }
 
-            self.update_size();
-            self.update_height();
+            if let Some(ref left) = node.left {
+                stack.push(left);
+            }
         }
+
+        res
     }
 
-    fn is_balance(&self) -> bool {
-        let diff = self.left().height() - self.right().height();
-        diff < 2 && diff > -2
+    // 中序遍历
+    fn in_order(&self) -> Vec<&Node<K, V>> {
+        let mut stack : Vec<&Node<K, V>> = Vec::new();
+        let mut res : Vec<&Node<K, V>> = Vec::new();
+        let mut p = self;
+
+        while p.is_some() || ! stack.is_empty() {
+            while let Some(ref node) = *p {
+                stack.push(node);
+                p = &node.left;
+            }
+
+            let cur = stack.pop().unwrap();
+            res.push(cur);
+            p = &cur.right;
+        }
+
+        res
+    }
+
+    // 后序遍历
+    fn post_order(&self) -> Vec<&Node<K, V>> {
+        let mut stack : Vec<&Node<K, V>> = Vec::new();
+        let mut res : Vec<&Node<K, V>> = Vec::new();
+
+        if let Some(ref node) = *self {
+            stack.push(node);
+        }
+
+        while let Some(node) = stack.pop() {
+            res.push(node);
+
+            if let Some(ref left) = node.left {
+                stack.push(left);
+            }
+
+            if let Some(ref right) = node.right {
+                stack.push(right);
+            }
+        }
+
+        res.reverse();
+        res
+    }
+
+    // 层级遍历
+    fn level_order(&self) -> Vec<&Node<K, V>> {
+        use std::collections::VecDeque;
+
+        let mut queue : VecDeque<&Node<K, V>> = VecDeque::new();
+        let mut res: Vec<&Node<K, V>> = Vec::new();
+
+        if let Some(ref node) = *self {
+            queue.push_back(node);
+        }
+
+        while let Some(node) = queue.pop_front() {
+            res.push(node);
+
+            if let Some(ref left) = node.left {
+                queue.push_back(left);
+            }
+
+            if let Some(ref right) = node.right {
+                queue.push_back(right);
+            }
+        }
+
+        res
     }
 }
 
@@ -204,13 +513,197 @@ impl<K : PartialOrd, V> AvlTree<K, V> {
         AvlTree { root: None }
     }
 
-    pub fn put(&mut self, key: K, val: V) {
-        self.root.put(key, val);
+    // 按键的顺序遍历键值对，支持双端迭代
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(self.root.as_deref(), self.root.size())
+    }
+
+    // 按键的顺序遍历键值对，可以修改值
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        let len = self.root.size();
+        IterMut::new(self.root.as_deref_mut(), len)
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys::new(self.iter())
+    }
+
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values::new(self.iter())
+    }
+
+    // 按键的顺序遍历范围内的键值对，如 tree.range(lo..hi)
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Range<'_, K, V, R> {
+        let len = self.range_count((range.start_bound(), range.end_bound()));
+        Range::new(self.root.as_deref(), range, len)
+    }
+
+    pub fn delete_min(&mut self) {
+        self.root.delete_min();
+    }
+
+    pub fn delete_max(&mut self) {
+        self.root.delete_max();
+    }
+
+    pub fn size(&self) -> usize {
+        self.root.size()
+    }
+
+    // 树的高度
+    pub fn height(&self) -> isize {
+        self.root.height()
+    }
+
+    pub fn pre_order(&self) -> Vec<&Node<K, V>> {
+        self.root.pre_order()
+    }
+
+    pub fn in_order(&self) -> Vec<&Node<K, V>> {
+        self.root.in_order()
+    }
+
+    pub fn post_order(&self) -> Vec<&Node<K, V>> {
+        self.root.post_order()
+    }
+
+    pub fn level_order(&self) -> Vec<&Node<K, V>> {
+        self.root.level_order()
     }
 
     pub fn is_balance(&self) -> bool {
         self.root.is_balance()
     }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.root.get(key)
+    }
+
+    pub fn put(&mut self, key: K, val: V) {
+        self.root.put(key, val);
+    }
+
+    pub fn delete(&mut self, key: &K) {
+        self.root.delete(key);
+    }
+
+    pub fn min(&self) -> Option<&K> {
+        self.root.min().as_ref().map(|node| &node.key)
+    }
+
+    pub fn max(&self) -> Option<&K> {
+        self.root.max().as_ref().map(|node| &node.key)
+    }
+
+    pub fn floor(&self, key: &K) -> Option<&K> {
+        self.root.floor(key).as_ref().map(|node| &node.key)
+    }
+
+    pub fn ceiling(&self, key: &K) -> Option<&K> {
+        self.root.ceiling(key).as_ref().map(|node| &node.key)
+    }
+
+    pub fn rank(&self, key: &K) -> usize {
+        self.root.rank(key)
+    }
+
+    pub fn select(&self, k: usize) -> Option<&K> {
+        self.root.select(k).as_ref().map(|node| &node.key)
+    }
+}
+
+impl<K : PartialOrd, V> OrderedMap<K, V> for AvlTree<K, V> {
+    fn get(&self, key: &K) -> Option<&V> {
+        Self::get(self, key)
+    }
+
+    fn put(&mut self, key: K, val: V) {
+        Self::put(self, key, val);
+    }
+
+    fn delete(&mut self, key: &K) {
+        Self::delete(self, key);
+    }
+
+    fn min(&self) -> Option<&K> {
+        Self::min(self)
+    }
+
+    fn max(&self) -> Option<&K> {
+        Self::max(self)
+    }
+
+    fn floor(&self, key: &K) -> Option<&K> {
+        Self::floor(self, key)
+    }
+
+    fn ceiling(&self, key: &K) -> Option<&K> {
+        Self::ceiling(self, key)
+    }
+
+    fn rank(&self, key: &K) -> usize {
+        Self::rank(self, key)
+    }
+
+    fn select(&self, k: usize) -> Option<&K> {
+        Self::select(self, k)
+    }
+
+    fn range<'a, R: RangeBounds<K> + 'a>(&'a self, range: R) -> Box<dyn Iterator<Item = (&'a K, &'a V)> + 'a> {
+        Box::new(Self::range(self, range))
+    }
+
+    fn len(&self) -> usize {
+        self.root.size()
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = (&'a K, &'a V)> + 'a> {
+        Box::new(Iter::new(self.root.as_deref(), self.root.size()))
+    }
+}
+
+impl<K : PartialOrd, V> IntoIterator for AvlTree<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        let len = self.root.size();
+        IntoIter::new(self.root, len)
+    }
+}
+
+impl<'a, K : PartialOrd, V> IntoIterator for &'a AvlTree<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K : PartialOrd, V> IntoIterator for &'a mut AvlTree<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+impl<K : PartialOrd, V> FromIterator<(K, V)> for AvlTree<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tree = AvlTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<K : PartialOrd, V> Extend<(K, V)> for AvlTree<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, val) in iter {
+            self.put(key, val);
+        }
+    }
 }
 
 #[test]
@@ -223,4 +716,118 @@ fn test() {
     }
 
     assert!(tree.is_balance());
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_map() {
+    let mut tree = AvlTree::<&str, isize>::new();
+    // A C E H M R S X
+    tree.put("S", 1);
+    tree.put("E", 2);
+    tree.put("X", 3);
+    tree.put("A", 4);
+    tree.put("R", 5);
+    tree.put("C", 6);
+    tree.put("H", 7);
+    tree.put("M", 8);
+
+    assert_eq!(tree.size(), 8);
+    assert_eq!(tree.get(&"S"), Some(&1));
+    assert_eq!(tree.get(&"B"), None);
+
+    // 更新值
+    tree.put("S", 9);
+    assert_eq!(tree.size(), 8);
+    assert_eq!(tree.get(&"S"), Some(&9));
+
+    // 不存在树中的key
+    assert_eq!(tree.floor(&"J"), Some(&"H"));
+    assert_eq!(tree.ceiling(&"J"), Some(&"M"));
+
+    // 存在树中的key
+    assert_eq!(tree.floor(&"R"), Some(&"R"));
+    assert_eq!(tree.ceiling(&"R"), Some(&"R"));
+
+    // 超出范围
+    assert_eq!(tree.floor(&"0"), None);
+    assert_eq!(tree.ceiling(&"Z"), None);
+
+    // 最小值和最大值
+    assert_eq!(tree.min(), Some(&"A"));
+    assert_eq!(tree.max(), Some(&"X"));
+
+    // 选择第k个元素，查看元素的排名
+    let keys = ["A", "C", "E", "H", "M", "R", "S", "X"];
+
+    for (i, key) in keys.iter().enumerate() {
+        assert_eq!(tree.select(i), Some(key));
+        assert_eq!(tree.rank(key), i);
+    }
+
+    assert_eq!(tree.select(8), None);
+    assert_eq!(tree.rank(&"J"), 4);
+
+    // 遍历
+    let in_order: Vec<&str> = tree.in_order().iter().map(|node| node.key).collect();
+    assert_eq!(in_order, keys);
+    assert_eq!(tree.pre_order().len(), 8);
+    assert_eq!(tree.post_order().len(), 8);
+    assert_eq!(tree.level_order()[0].key, tree.pre_order()[0].key);
+    assert_eq!(tree.post_order()[7].key, tree.pre_order()[0].key);
+
+    // 删除最小元素
+    tree.delete_min();
+    assert_eq!(tree.size(), 7);
+    assert!(tree.get(&"A").is_none());
+    assert_eq!(tree.min(), Some(&"C"));
+
+    // 删除最大元素
+    tree.delete_max();
+    assert_eq!(tree.size(), 6);
+    assert!(tree.get(&"X").is_none());
+    assert_eq!(tree.max(), Some(&"S"));
+
+    // 根据key删除元素
+    tree.delete(&"E");
+    tree.delete(&"B");
+    assert_eq!(tree.size(), 5);
+    assert!(tree.get(&"E").is_none());
+    assert!(tree.is_balance());
+
+    let in_order: Vec<&str> = tree.in_order().iter().map(|node| node.key).collect();
+    assert_eq!(in_order, ["C", "H", "M", "R", "S"]);
+}
+
+#[test]
+fn test_delete_balance() {
+    let mut tree = AvlTree::<usize, usize>::new();
+
+    for i in 0..1000 {
+        tree.put(i, i * 2);
+    }
+
+    assert!(tree.is_balance());
+    assert!(tree.height() <= 15);
+
+    // 删除偶数
+    for i in 0..500 {
+        tree.delete(&(i * 2));
+        assert!(tree.is_balance());
+    }
+
+    assert_eq!(tree.size(), 500);
+    assert_eq!(tree.get(&7), Some(&14));
+    assert_eq!(tree.get(&8), None);
+    assert_eq!(tree.rank(&501), 250);
+
+    while tree.size() > 0 {
+        tree.delete_min();
+        tree.delete_max();
+        assert!(tree.is_balance());
+    }
+
+    assert_eq!(tree.min(), None);
+    tree.delete_min();
+    tree.delete_max();
+    assert_eq!(tree.size(), 0);
+}
@@ -1,4 +1,8 @@
 use std::mem;
+use std::iter::FromIterator;
+use std::ops::RangeBounds;
+use super::ordered_map::OrderedMap;
+use super::iter::{self, TreeNode};
 
 pub type Link<K, V> = Option<Box<Node<K, V>>>;
 
@@ -11,22 +15,43 @@ pub struct Node<K, V> {
     n: usize,
 }
 
+pub type Iter<'a, K, V> = iter::Iter<'a, K, V, Node<K, V>>;
+pub type IterMut<'a, K, V> = iter::IterMut<'a, K, V, Node<K, V>>;
+pub type IntoIter<K, V> = iter::IntoIter<K, V, Node<K, V>>;
+pub type Keys<'a, K, V> = iter::Keys<'a, K, V, Node<K, V>>;
+pub type Values<'a, K, V> = iter::Values<'a, K, V, Node<K, V>>;
+pub type Range<'a, K, V, R> = iter::Range<'a, K, V, Node<K, V>, R>;
+
+impl<K, V> TreeNode<K, V> for Node<K, V> {
+    fn parts(&self) -> (&K, &V, Option<&Self>, Option<&Self>) {
+        (&self.key, &self.val, self.left.as_deref(), self.right.as_deref())
+    }
+
+    fn parts_mut(&mut self) -> (&K, &mut V, Option<&mut Self>, Option<&mut Self>) {
+        (&self.key, &mut self.val, self.left.as_deref_mut(), self.right.as_deref_mut())
+    }
+
+    fn into_parts(self) -> (K, V, Option<Box<Self>>, Option<Box<Self>>) {
+        (self.key, self.val, self.left, self.right)
+    }
+}
+
 pub trait ST<K, V> {
     fn new(key: K, val: V) -> Link<K, V>;
     fn size(&self) -> usize;
-    fn get(&self, key: K) -> &Link<K, V>;
-    fn get_mut(&mut self, key: K) -> &mut Link<K, V>;
+    fn get(&self, key: &K) -> &Link<K, V>;
+    fn get_mut(&mut self, key: &K) -> &mut Link<K, V>;
     fn put(&mut self, key: K, val: V);
     fn min(&self) -> &Link<K, V>;
     fn min_mut(&mut self) -> &mut Link<K, V>;
     fn max(&self) -> &Link<K, V>;
-    fn ceiling(&self, key: K) -> &Link<K, V>;
-    fn floor(&self, key: K) -> &Link<K, V>;
+    fn ceiling(&self, key: &K) -> &Link<K, V>;
+    fn floor(&self, key: &K) -> &Link<K, V>;
     fn select(&self, k: usize) -> &Link<K, V>;
-    fn rank(&self, key: K) -> usize;
+    fn rank(&self, key: &K) -> usize;
     fn delete_min(&mut self);
     fn delete_max(&mut self);
-    fn delete(&mut self, key: K);
+    fn delete(&mut self, key: &K);
     fn delete_self(&mut self);
 }
 
@@ -51,31 +76,30 @@ impl<K: PartialOrd, V> ST<K, V> for Link<K, V> {
         }
     }
 
-    fn get(&self, key: K) -> &Self {
-        match {self} {
-            &Some(ref node) if key != node.key => {
-                if key < node.key {
+    fn get(&self, key: &K) -> &Self {
+        match self {
+            Some(node) if *key != node.key => {
+                if *key < node.key {
                     node.left.get(key)
                 }
                 else {
                     node.right.get(key)
                 }
             },
-            node @ &Some(_) | node @ &None => node,
+            _ => self,
         }
     }
 
-    fn get_mut(&mut self, key: K) -> &mut Self {
-        match {self} {
-            &mut Some(ref mut node) if key != node.key => {
-                if key < node.key {
-                    node.left.get_mut(key)
-                }
-                else {
-                    node.right.get_mut(key)
-                }
-            },
-            node @ &mut Some(_) | node @ &mut None => node,
+    fn get_mut(&mut self, key: &K) -> &mut Self {
+        let go_left = match *self {
+            Some(ref node) if *key != node.key => Some(*key < node.key),
+            _ => None,
+        };
+
+        match go_left {
+            Some(true) => self.as_mut().unwrap().left.get_mut(key),
+            Some(false) => self.as_mut().unwrap().right.get_mut(key),
+            None => self,
         }
     }
 
@@ -118,11 +142,16 @@ impl<K: PartialOrd, V> ST<K, V> for Link<K, V> {
     }
 
     fn min_mut(&mut self) -> &mut Self {
-        match {self} {
-            &mut Some(ref mut node) if node.left.is_some() => {
-                node.left.min_mut()
-            },
-            other @ &mut Some(_) | other @ &mut None => other,
+        let has_left = match *self {
+            Some(ref node) => node.left.is_some(),
+            None => false,
+        };
+
+        if has_left {
+            self.as_mut().unwrap().left.min_mut()
+        }
+        else {
+            self
         }
     }
 
@@ -135,10 +164,10 @@ impl<K: PartialOrd, V> ST<K, V> for Link<K, V> {
         }
     }
 
-    fn ceiling(&self, key: K) -> &Self {
+    fn ceiling(&self, key: &K) -> &Self {
         match *self {
             Some(ref node) => {
-                if key < node.key {
+                if *key < node.key {
                     let tree_node = node.left.ceiling(key);
 
                     if tree_node.is_none() {
@@ -148,7 +177,7 @@ impl<K: PartialOrd, V> ST<K, V> for Link<K, V> {
                         tree_node
                     }
                 }
-                else if key > node.key {
+                else if *key > node.key {
                     node.right.ceiling(key)
                 }
                 else {
@@ -159,13 +188,13 @@ impl<K: PartialOrd, V> ST<K, V> for Link<K, V> {
         }
     }
 
-    fn floor(&self, key: K) -> &Self {
+    fn floor(&self, key: &K) -> &Self {
         match *self {
             Some(ref node) => {
-                if key < node.key {
+                if *key < node.key {
                     node.left.floor(key)
                 }
-                else if key > node.key {
+                else if *key > node.key {
                     let tree_node = node.right.floor(key);
 
                     if tree_node.is_none() {
@@ -202,13 +231,13 @@ impl<K: PartialOrd, V> ST<K, V> for Link<K, V> {
         }
     }
 
-    fn rank(&self, key: K) -> usize {
+    fn rank(&self, key: &K) -> usize {
         match *self {
             Some(ref node) => {
-                if key < node.key {
+                if *key < node.key {
                     node.left.rank(key)
                 }
-                else if key > node.key {
+                else if *key > node.key {
                     1 + node.left.size() + node.right.rank(key)
                 }
                 else {
@@ -261,14 +290,14 @@ impl<K: PartialOrd, V> ST<K, V> for Link<K, V> {
         }
     }
 
-    fn delete(&mut self, key: K) {
+    fn delete(&mut self, key: &K) {
         let mut is_self = false;
 
         if let &mut Some(ref mut node) = self {
-            if key < node.key {
+            if *key < node.key {
                 node.left.delete(key);
             }
-            else if key > node.key {
+            else if *key > node.key {
                 node.right.delete(key);
             }
             else {
@@ -308,6 +337,7 @@ impl<K: PartialOrd, V> ST<K, V> for Link<K, V> {
             }
         }
     }
+
 }
 
 
@@ -320,52 +350,171 @@ impl<K: PartialOrd, V> BinarySearchTree<K, V> {
         BinarySearchTree { root: None }
     }
 
+    // 按键的顺序遍历键值对，支持双端迭代
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(self.root.as_deref(), self.root.size())
+    }
+
+    // 按键的顺序遍历键值对，可以修改值
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        let len = self.root.size();
+        IterMut::new(self.root.as_deref_mut(), len)
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys::new(self.iter())
+    }
+
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values::new(self.iter())
+    }
+
+    // 按键的顺序遍历范围内的键值对，如 tree.range(lo..hi)
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Range<'_, K, V, R> {
+        let len = self.range_count((range.start_bound(), range.end_bound()));
+        Range::new(self.root.as_deref(), range, len)
+    }
+
     pub fn size(&self) -> usize {
         self.root.size()
     }
 
+    pub fn delete_min(&mut self) {
+        self.root.delete_min()
+    }
+
+    pub fn delete_max(&mut self) {
+        self.root.delete_max()
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.root.get(key).as_ref().map(|node| &node.val)
+    }
+
     pub fn put(&mut self, key: K, val: V) {
         self.root.put(key, val)
     }
 
-    pub fn get(&self, key: K) -> &Link<K, V> {
-        self.root.get(key)
+    pub fn delete(&mut self, key: &K) {
+        self.root.delete(key)
     }
 
-    pub fn min(&self) -> &Link<K, V> {
-        self.root.min()
+    pub fn min(&self) -> Option<&K> {
+        self.root.min().as_ref().map(|node| &node.key)
     }
 
-    pub fn max(&self) -> &Link<K, V> {
-        self.root.max()
+    pub fn max(&self) -> Option<&K> {
+        self.root.max().as_ref().map(|node| &node.key)
     }
 
-    pub fn floor(&self, key: K) -> &Link<K, V> {
-        self.root.floor(key)
+    pub fn floor(&self, key: &K) -> Option<&K> {
+        self.root.floor(key).as_ref().map(|node| &node.key)
     }
 
-    pub fn ceiling(&self, key: K) -> &Link<K, V> {
-        self.root.ceiling(key)
+    pub fn ceiling(&self, key: &K) -> Option<&K> {
+        self.root.ceiling(key).as_ref().map(|node| &node.key)
     }
 
-    pub fn select(&self, k: usize) -> &Link<K, V> {
-        self.root.select(k)
+    pub fn rank(&self, key: &K) -> usize {
+        self.root.rank(key)
     }
 
-    pub fn rank(&self, key: K) -> usize {
-        self.root.rank(key)
+    pub fn select(&self, k: usize) -> Option<&K> {
+        self.root.select(k).as_ref().map(|node| &node.key)
     }
+}
 
-    pub fn delete_min(&mut self) {
-        self.root.delete_min()
+impl<K: PartialOrd, V> OrderedMap<K, V> for BinarySearchTree<K, V> {
+    fn get(&self, key: &K) -> Option<&V> {
+        Self::get(self, key)
     }
 
-    pub fn delete_max(&mut self) {
-        self.root.delete_max()
+    fn put(&mut self, key: K, val: V) {
+        Self::put(self, key, val);
     }
 
-    pub fn delete(&mut self, key: K) {
-        self.root.delete(key)
+    fn delete(&mut self, key: &K) {
+        Self::delete(self, key);
+    }
+
+    fn min(&self) -> Option<&K> {
+        Self::min(self)
+    }
+
+    fn max(&self) -> Option<&K> {
+        Self::max(self)
+    }
+
+    fn floor(&self, key: &K) -> Option<&K> {
+        Self::floor(self, key)
+    }
+
+    fn ceiling(&self, key: &K) -> Option<&K> {
+        Self::ceiling(self, key)
+    }
+
+    fn rank(&self, key: &K) -> usize {
+        Self::rank(self, key)
+    }
+
+    fn select(&self, k: usize) -> Option<&K> {
+        Self::select(self, k)
+    }
+
+    fn range<'a, R: RangeBounds<K> + 'a>(&'a self, range: R) -> Box<dyn Iterator<Item = (&'a K, &'a V)> + 'a> {
+        Box::new(Self::range(self, range))
+    }
+
+    fn len(&self) -> usize {
+        self.root.size()
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = (&'a K, &'a V)> + 'a> {
+        Box::new(Iter::new(self.root.as_deref(), self.root.size()))
+    }
+}
+
+impl<K: PartialOrd, V> IntoIterator for BinarySearchTree<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        let len = self.root.size();
+        IntoIter::new(self.root, len)
+    }
+}
+
+impl<'a, K: PartialOrd, V> IntoIterator for &'a BinarySearchTree<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K: PartialOrd, V> IntoIterator for &'a mut BinarySearchTree<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+impl<K: PartialOrd, V> FromIterator<(K, V)> for BinarySearchTree<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tree = BinarySearchTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<K: PartialOrd, V> Extend<(K, V)> for BinarySearchTree<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, val) in iter {
+            self.put(key, val);
+        }
     }
 }
 
@@ -385,60 +534,24 @@ fn test() {
 
     assert_eq!(bst.size(), 8);
 
-
-    match *bst.get("C") {
-        Some(ref node) => assert_eq!(node.val, 6),
-        None => assert!(false),
-    }
-
-    match *bst.min() {
-        Some(ref node) => assert_eq!(node.key, "A"),
-        None => assert!(false),
-    }
-
-    match *bst.max() {
-        Some(ref node) => assert_eq!(node.key, "X"),
-        None => assert!(false),
-    }
-
-    match *bst.floor("G") {
-        Some(ref node) => assert_eq!(node.key, "E"),
-        None => assert!(false),
-    }
-
-    match *bst.ceiling("G") {
-        Some(ref node) => assert_eq!(node.key, "H"),
-        None => assert!(false),
-    }
-
-    match *bst.select(5) {
-        Some(ref node) => assert_eq!(node.key, "R"),
-        None => assert!(false),
-    }
-
-    assert_eq!(bst.rank("R"), 5);
+    assert_eq!(bst.get(&"C"), Some(&6));
+    assert_eq!(bst.min(), Some(&"A"));
+    assert_eq!(bst.max(), Some(&"X"));
+    assert_eq!(bst.floor(&"G"), Some(&"E"));
+    assert_eq!(bst.ceiling(&"G"), Some(&"H"));
+    assert_eq!(bst.select(5), Some(&"R"));
+    assert_eq!(bst.rank(&"R"), 5);
 
     bst.delete_min();
     assert_eq!(bst.size(), 7);
-
-    match *bst.min() {
-        Some(ref node) => assert_eq!(node.key, "C"),
-        None => assert!(false),
-    }
+    assert_eq!(bst.min(), Some(&"C"));
 
     bst.delete_max();
     assert_eq!(bst.size(), 6);
+    assert_eq!(bst.max(), Some(&"S"));
 
-    match *bst.max() {
-        Some(ref node) => assert_eq!(node.key, "S"),
-        None => assert!(false),
-    }
-
-    bst.delete("E");
-    match *bst.get("E") {
-        Some(_) => assert!(false),
-        None => assert!(true),
-    }
+    bst.delete(&"E");
+    assert_eq!(bst.get(&"E"), None);
 
     assert_eq!(bst.size(), 5);
-}
\ No newline at end of file
+}
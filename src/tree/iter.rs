@@ -0,0 +1,528 @@
+// 树的中序迭代器，三种树共用
+// 用一个双端队列保存尚未展开的子树和已经展开的键值对，
+// 从前端取时展开最左侧的子树，从后端取时展开最右侧的子树，
+// 每个节点只会被展开一次，队列长度为 O(h)
+use std::collections::VecDeque;
+use std::iter::FusedIterator;
+use std::ops::{Bound, RangeBounds};
+
+// 树节点，拆分成键、值、左子树和右子树
+pub trait TreeNode<K, V>: Sized {
+    fn parts(&self) -> (&K, &V, Option<&Self>, Option<&Self>);
+    fn parts_mut(&mut self) -> (&K, &mut V, Option<&mut Self>, Option<&mut Self>);
+    fn into_parts(self) -> (K, V, Option<Box<Self>>, Option<Box<Self>>);
+}
+
+enum Item<N, E> {
+    Node(N),        // 尚未展开的子树
+    Entry(E),       // 已经展开的键值对
+}
+
+// 借用迭代器
+pub struct Iter<'a, K: 'a, V: 'a, N: 'a> {
+    items: VecDeque<Item<&'a N, (&'a K, &'a V)>>,
+    len: usize,
+}
+
+impl<'a, K, V, N: TreeNode<K, V>> Iter<'a, K, V, N> {
+    pub fn new(root: Option<&'a N>, len: usize) -> Self {
+        let mut items = VecDeque::new();
+
+        if let Some(node) = root {
+            items.push_back(Item::Node(node));
+        }
+
+        Iter { items, len }
+    }
+}
+
+impl<'a, K, V, N: TreeNode<K, V>> Iterator for Iter<'a, K, V, N> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(item) = self.items.pop_front() {
+            match item {
+                Item::Entry(entry) => {
+                    self.len -= 1;
+                    return Some(entry)
+                },
+                Item::Node(node) => {
+                    let (key, val, left, right) = node.parts();
+
+                    if let Some(right) = right {
+                        self.items.push_front(Item::Node(right));
+                    }
+
+                    self.items.push_front(Item::Entry((key, val)));
+
+                    if let Some(left) = left {
+                        self.items.push_front(Item::Node(left));
+                    }
+                },
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, K, V, N: TreeNode<K, V>> DoubleEndedIterator for Iter<'a, K, V, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some(item) = self.items.pop_back() {
+            match item {
+                Item::Entry(entry) => {
+                    self.len -= 1;
+                    return Some(entry)
+                },
+                Item::Node(node) => {
+                    let (key, val, left, right) = node.parts();
+
+                    if let Some(left) = left {
+                        self.items.push_back(Item::Node(left));
+                    }
+
+                    self.items.push_back(Item::Entry((key, val)));
+
+                    if let Some(right) = right {
+                        self.items.push_back(Item::Node(right));
+                    }
+                },
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a, K, V, N: TreeNode<K, V>> ExactSizeIterator for Iter<'a, K, V, N> {}
+
+impl<'a, K, V, N: TreeNode<K, V>> FusedIterator for Iter<'a, K, V, N> {}
+
+// 可变借用迭代器，只能修改值
+pub struct IterMut<'a, K: 'a, V: 'a, N: 'a> {
+    items: VecDeque<Item<&'a mut N, (&'a K, &'a mut V)>>,
+    len: usize,
+}
+
+impl<'a, K, V, N: TreeNode<K, V>> IterMut<'a, K, V, N> {
+    pub fn new(root: Option<&'a mut N>, len: usize) -> Self {
+        let mut items = VecDeque::new();
+
+        if let Some(node) = root {
+            items.push_back(Item::Node(node));
+        }
+
+        IterMut { items, len }
+    }
+}
+
+impl<'a, K, V, N: TreeNode<K, V>> Iterator for IterMut<'a, K, V, N> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(item) = self.items.pop_front() {
+            match item {
+                Item::Entry(entry) => {
+                    self.len -= 1;
+                    return Some(entry)
+                },
+                Item::Node(node) => {
+                    let (key, val, left, right) = node.parts_mut();
+
+                    if let Some(right) = right {
+                        self.items.push_front(Item::Node(right));
+                    }
+
+                    self.items.push_front(Item::Entry((key, val)));
+
+                    if let Some(left) = left {
+                        self.items.push_front(Item::Node(left));
+                    }
+                },
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, K, V, N: TreeNode<K, V>> DoubleEndedIterator for IterMut<'a, K, V, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some(item) = self.items.pop_back() {
+            match item {
+                Item::Entry(entry) => {
+                    self.len -= 1;
+                    return Some(entry)
+                },
+                Item::Node(node) => {
+                    let (key, val, left, right) = node.parts_mut();
+
+                    if let Some(left) = left {
+                        self.items.push_back(Item::Node(left));
+                    }
+
+                    self.items.push_back(Item::Entry((key, val)));
+
+                    if let Some(right) = right {
+                        self.items.push_back(Item::Node(right));
+                    }
+                },
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a, K, V, N: TreeNode<K, V>> ExactSizeIterator for IterMut<'a, K, V, N> {}
+
+impl<'a, K, V, N: TreeNode<K, V>> FusedIterator for IterMut<'a, K, V, N> {}
+
+// 获取所有权的迭代器
+pub struct IntoIter<K, V, N> {
+    items: VecDeque<Item<Box<N>, (K, V)>>,
+    len: usize,
+}
+
+impl<K, V, N: TreeNode<K, V>> IntoIter<K, V, N> {
+    pub fn new(root: Option<Box<N>>, len: usize) -> Self {
+        let mut items = VecDeque::new();
+
+        if let Some(node) = root {
+            items.push_back(Item::Node(node));
+        }
+
+        IntoIter { items, len }
+    }
+}
+
+impl<K, V, N: TreeNode<K, V>> Iterator for IntoIter<K, V, N> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(item) = self.items.pop_front() {
+            match item {
+                Item::Entry(entry) => {
+                    self.len -= 1;
+                    return Some(entry)
+                },
+                Item::Node(node) => {
+                    let (key, val, left, right) = node.into_parts();
+
+                    if let Some(right) = right {
+                        self.items.push_front(Item::Node(right));
+                    }
+
+                    self.items.push_front(Item::Entry((key, val)));
+
+                    if let Some(left) = left {
+                        self.items.push_front(Item::Node(left));
+                    }
+                },
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V, N: TreeNode<K, V>> DoubleEndedIterator for IntoIter<K, V, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some(item) = self.items.pop_back() {
+            match item {
+                Item::Entry(entry) => {
+                    self.len -= 1;
+                    return Some(entry)
+                },
+                Item::Node(node) => {
+                    let (key, val, left, right) = node.into_parts();
+
+                    if let Some(left) = left {
+                        self.items.push_back(Item::Node(left));
+                    }
+
+                    self.items.push_back(Item::Entry((key, val)));
+
+                    if let Some(right) = right {
+                        self.items.push_back(Item::Node(right));
+                    }
+                },
+            }
+        }
+
+        None
+    }
+}
+
+impl<K, V, N: TreeNode<K, V>> ExactSizeIterator for IntoIter<K, V, N> {}
+
+impl<K, V, N: TreeNode<K, V>> FusedIterator for IntoIter<K, V, N> {}
+
+// 键迭代器
+pub struct Keys<'a, K: 'a, V: 'a, N: 'a> {
+    inner: Iter<'a, K, V, N>,
+}
+
+impl<'a, K, V, N: TreeNode<K, V>> Keys<'a, K, V, N> {
+    pub fn new(inner: Iter<'a, K, V, N>) -> Self {
+        Keys { inner }
+    }
+}
+
+impl<'a, K, V, N: TreeNode<K, V>> Iterator for Keys<'a, K, V, N> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        self.inner.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V, N: TreeNode<K, V>> DoubleEndedIterator for Keys<'a, K, V, N> {
+    fn next_back(&mut self) -> Option<&'a K> {
+        self.inner.next_back().map(|(key, _)| key)
+    }
+}
+
+impl<'a, K, V, N: TreeNode<K, V>> ExactSizeIterator for Keys<'a, K, V, N> {}
+
+impl<'a, K, V, N: TreeNode<K, V>> FusedIterator for Keys<'a, K, V, N> {}
+
+// 值迭代器
+pub struct Values<'a, K: 'a, V: 'a, N: 'a> {
+    inner: Iter<'a, K, V, N>,
+}
+
+impl<'a, K, V, N: TreeNode<K, V>> Values<'a, K, V, N> {
+    pub fn new(inner: Iter<'a, K, V, N>) -> Self {
+        Values { inner }
+    }
+}
+
+impl<'a, K, V, N: TreeNode<K, V>> Iterator for Values<'a, K, V, N> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<&'a V> {
+        self.inner.next().map(|(_, val)| val)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V, N: TreeNode<K, V>> DoubleEndedIterator for Values<'a, K, V, N> {
+    fn next_back(&mut self) -> Option<&'a V> {
+        self.inner.next_back().map(|(_, val)| val)
+    }
+}
+
+impl<'a, K, V, N: TreeNode<K, V>> ExactSizeIterator for Values<'a, K, V, N> {}
+
+impl<'a, K, V, N: TreeNode<K, V>> FusedIterator for Values<'a, K, V, N> {}
+
+// 范围迭代器，只展开可能包含范围内键的子树
+pub struct Range<'a, K: 'a, V: 'a, N: 'a, R> {
+    items: VecDeque<Item<&'a N, (&'a K, &'a V)>>,
+    range: R,
+    len: usize,
+}
+
+impl<'a, K: PartialOrd, V, N: TreeNode<K, V>, R: RangeBounds<K>> Range<'a, K, V, N, R> {
+    // len 为范围内键的数量
+    pub fn new(root: Option<&'a N>, range: R, len: usize) -> Self {
+        let mut items = VecDeque::new();
+
+        if let Some(node) = root {
+            items.push_back(Item::Node(node));
+        }
+
+        Range { items, range, len }
+    }
+
+    fn expand(&mut self, node: &'a N, back: bool) {
+        let (key, val, left, right) = node.parts();
+
+        // 当前键不大于下界时，左子树不会有范围内的键
+        let left = match self.range.start_bound() {
+            Bound::Included(lo) | Bound::Excluded(lo) if key <= lo => None,
+            _ => left,
+        };
+
+        // 当前键不小于上界时，右子树不会有范围内的键
+        let right = match self.range.end_bound() {
+            Bound::Included(hi) | Bound::Excluded(hi) if key >= hi => None,
+            _ => right,
+        };
+
+        let entry = if self.range.contains(key) {
+            Some(Item::Entry((key, val)))
+        }
+        else {
+            None
+        };
+
+        if back {
+            self.items.extend(left.map(Item::Node));
+            self.items.extend(entry);
+            self.items.extend(right.map(Item::Node));
+        }
+        else {
+            if let Some(right) = right {
+                self.items.push_front(Item::Node(right));
+            }
+
+            if let Some(entry) = entry {
+                self.items.push_front(entry);
+            }
+
+            if let Some(left) = left {
+                self.items.push_front(Item::Node(left));
+            }
+        }
+    }
+}
+
+impl<'a, K: PartialOrd, V, N: TreeNode<K, V>, R: RangeBounds<K>> Iterator for Range<'a, K, V, N, R> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(item) = self.items.pop_front() {
+            match item {
+                Item::Entry(entry) => {
+                    self.len -= 1;
+                    return Some(entry)
+                },
+                Item::Node(node) => self.expand(node, false),
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, K: PartialOrd, V, N: TreeNode<K, V>, R: RangeBounds<K>> DoubleEndedIterator for Range<'a, K, V, N, R> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some(item) = self.items.pop_back() {
+            match item {
+                Item::Entry(entry) => {
+                    self.len -= 1;
+                    return Some(entry)
+                },
+                Item::Node(node) => self.expand(node, true),
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a, K: PartialOrd, V, N: TreeNode<K, V>, R: RangeBounds<K>> ExactSizeIterator for Range<'a, K, V, N, R> {}
+
+impl<'a, K: PartialOrd, V, N: TreeNode<K, V>, R: RangeBounds<K>> FusedIterator for Range<'a, K, V, N, R> {}
+
+#[cfg(test)]
+macro_rules! test_iter {
+    ($tree:ident) => ({
+        let keys = [5, 2, 8, 1, 9, 3, 7, 4, 6, 0];
+        let mut tree: $tree<i32, String> = keys.iter().map(|k| (*k, k.to_string())).collect();
+
+        // 中序迭代
+        let all: Vec<(&i32, &String)> = tree.iter().collect();
+        assert_eq!(all.len(), 10);
+        assert_eq!(tree.iter().len(), 10);
+        assert_eq!(tree.keys().cloned().collect::<Vec<i32>>(), (0..10).collect::<Vec<i32>>());
+        assert_eq!(tree.values().next(), Some(&"0".to_string()));
+
+        // 反向迭代
+        assert_eq!(tree.keys().rev().cloned().collect::<Vec<i32>>(), (0..10).rev().collect::<Vec<i32>>());
+
+        // 两端交替迭代，不会重复也不会遗漏
+        let mut it = tree.keys();
+        let mut seen = Vec::new();
+
+        loop {
+            match (it.next(), it.next_back()) {
+                (Some(a), Some(b)) => { seen.push(*a); seen.push(*b); },
+                (Some(a), None) | (None, Some(a)) => seen.push(*a),
+                (None, None) => break,
+            }
+        }
+
+        assert_eq!(it.len(), 0);
+        assert_eq!(it.next(), None);
+        seen.sort();
+        assert_eq!(seen, (0..10).collect::<Vec<i32>>());
+
+        // 修改值
+        for (key, val) in tree.iter_mut() {
+            val.push_str(&key.to_string());
+        }
+
+        for (_, val) in &mut tree {
+            val.push('!');
+        }
+
+        assert_eq!(tree.get(&7), Some(&"77!".to_string()));
+
+        let mut count = 0;
+
+        for (key, val) in &tree {
+            assert_eq!(*val, format!("{}{}!", key, key));
+            count += 1;
+        }
+
+        assert_eq!(count, 10);
+
+        // 批量插入
+        tree.extend(vec![(10, "10".to_string()), (3, "3".to_string())]);
+        assert_eq!(tree.len(), 11);
+        assert_eq!(tree.get(&3), Some(&"3".to_string()));
+
+        // 获取所有权
+        let mut into_iter = tree.into_iter();
+        assert_eq!(into_iter.next(), Some((0, "00!".to_string())));
+        assert_eq!(into_iter.next_back(), Some((10, "10".to_string())));
+        assert_eq!(into_iter.len(), 9);
+
+        let rest: Vec<i32> = into_iter.map(|(k, _)| k).collect();
+        assert_eq!(rest, (1..10).collect::<Vec<i32>>());
+
+        // 空树
+        let empty = $tree::<i32, i32>::new();
+        assert_eq!(empty.iter().next(), None);
+        assert_eq!(empty.iter().next_back(), None);
+        assert_eq!(empty.into_iter().count(), 0);
+    })
+}
+
+#[test]
+fn test() {
+    use super::ordered_map::OrderedMap;
+    use super::binary_search_tree::BinarySearchTree;
+    use super::avl_tree::AvlTree;
+    use super::red_black_tree::RedBlackTree;
+
+    test_iter!(BinarySearchTree);
+    test_iter!(AvlTree);
+    test_iter!(RedBlackTree);
+}
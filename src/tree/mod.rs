@@ -1,3 +1,6 @@
 pub mod binary_search_tree;
 pub mod avl_tree;
 pub mod red_black_tree;
+
+pub mod ordered_map;
+pub mod iter;
\ No newline at end of file
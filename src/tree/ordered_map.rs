@@ -0,0 +1,223 @@
+use std::ops::{Bound, RangeBounds};
+
+// 有序符号表
+// BinarySearchTree、AvlTree 和 RedBlackTree 都实现了这个接口，可以相互替换
+pub trait OrderedMap<K, V> {
+    // 获取键对应的值
+    fn get(&self, key: &K) -> Option<&V>;
+
+    // 插入键值对，键已存在时更新值
+    fn put(&mut self, key: K, val: V);
+
+    // 删除键，键不存在时什么也不做
+    fn delete(&mut self, key: &K);
+
+    // 是否包含键
+    fn contains(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    // 最小的键
+    fn min(&self) -> Option<&K>;
+
+    // 最大的键
+    fn max(&self) -> Option<&K>;
+
+    // 小于等于 key 的最大键
+    fn floor(&self, key: &K) -> Option<&K>;
+
+    // 大于等于 key 的最小键
+    fn ceiling(&self, key: &K) -> Option<&K>;
+
+    // 小于 key 的键的数量
+    fn rank(&self, key: &K) -> usize;
+
+    // 排名为 k 的键
+    fn select(&self, k: usize) -> Option<&K>;
+
+    // 范围内的键值对，按键的顺序排列，如 map.range(lo..hi)
+    fn range<'a, R: RangeBounds<K> + 'a>(&'a self, range: R) -> Box<dyn Iterator<Item = (&'a K, &'a V)> + 'a>
+        where Self: Sized;
+
+    // 范围内键的数量，由两次 rank 相减得到
+    fn range_count<R: RangeBounds<K>>(&self, range: R) -> usize
+        where Self: Sized
+    {
+        let lo = match range.start_bound() {
+            Bound::Included(lo) => self.rank(lo),
+            Bound::Excluded(lo) => self.rank(lo) + self.contains(lo) as usize,
+            Bound::Unbounded => 0,
+        };
+
+        let hi = match range.end_bound() {
+            Bound::Included(hi) => self.rank(hi) + self.contains(hi) as usize,
+            Bound::Excluded(hi) => self.rank(hi),
+            Bound::Unbounded => self.len(),
+        };
+
+        hi.saturating_sub(lo)
+    }
+
+    // 键值对数量
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // 按键的顺序遍历键值对
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = (&'a K, &'a V)> + 'a>;
+}
+
+// 所有实现共用的一致性测试
+#[cfg(test)]
+fn conformance<M: OrderedMap<&'static str, isize>>(mut map: M) {
+    assert!(map.is_empty());
+    assert_eq!(map.min(), None);
+    assert_eq!(map.max(), None);
+    assert_eq!(map.floor(&"J"), None);
+    assert_eq!(map.select(0), None);
+    assert_eq!(map.rank(&"J"), 0);
+
+    // 空表中删除
+    map.delete(&"J");
+    assert_eq!(map.len(), 0);
+
+    // A C E H M R S X
+    for (i, key) in ["S", "E", "X", "A", "R", "C", "H", "M"].iter().enumerate() {
+        map.put(*key, i as isize);
+    }
+
+    assert_eq!(map.len(), 8);
+    assert!(! map.is_empty());
+    assert_eq!(map.get(&"S"), Some(&0));
+    assert_eq!(map.get(&"M"), Some(&7));
+    assert_eq!(map.get(&"B"), None);
+    assert!(map.contains(&"R"));
+    assert!(! map.contains(&"B"));
+
+    // 更新值
+    map.put("S", 10);
+    assert_eq!(map.len(), 8);
+    assert_eq!(map.get(&"S"), Some(&10));
+
+    assert_eq!(map.min(), Some(&"A"));
+    assert_eq!(map.max(), Some(&"X"));
+
+    assert_eq!(map.floor(&"J"), Some(&"H"));
+    assert_eq!(map.ceiling(&"J"), Some(&"M"));
+    assert_eq!(map.floor(&"R"), Some(&"R"));
+    assert_eq!(map.ceiling(&"R"), Some(&"R"));
+    assert_eq!(map.floor(&"0"), None);
+    assert_eq!(map.ceiling(&"Z"), None);
+
+    let keys = ["A", "C", "E", "H", "M", "R", "S", "X"];
+
+    for (i, key) in keys.iter().enumerate() {
+        assert_eq!(map.select(i), Some(key));
+        assert_eq!(map.rank(key), i);
+    }
+
+    assert_eq!(map.select(8), None);
+    assert_eq!(map.rank(&"J"), 4);
+    assert_eq!(map.rank(&"Z"), 8);
+
+    let range: Vec<&str> = map.range("D"..="R").map(|(k, _)| *k).collect();
+    assert_eq!(range, ["E", "H", "M", "R"]);
+    assert_eq!(map.range("D".."R").count(), 3);
+    assert_eq!(map.range("R"..="D").count(), 0);
+    assert_eq!(map.range(..).count(), 8);
+    assert_eq!(map.range_count("D"..="R"), 4);
+    assert_eq!(map.range_count("D".."R"), 3);
+    assert_eq!(map.range_count("R"..="D"), 0);
+    assert_eq!(map.range_count(..), 8);
+
+    let all: Vec<&str> = map.iter().map(|(k, _)| *k).collect();
+    assert_eq!(all, keys);
+    assert_eq!(map.iter().map(|(_, v)| *v).sum::<isize>(), 38);
+
+    // 删除
+    map.delete(&"E");
+    map.delete(&"B");
+    assert_eq!(map.len(), 7);
+    assert!(! map.contains(&"E"));
+    assert_eq!(map.floor(&"G"), Some(&"C"));
+
+    for key in keys.iter() {
+        map.delete(key);
+    }
+
+    assert!(map.is_empty());
+    assert_eq!(map.iter().count(), 0);
+}
+
+#[cfg(test)]
+fn conformance_random<M: OrderedMap<u32, u32>>(mut map: M) {
+    extern crate rand;
+    use std::collections::BTreeMap;
+
+    let mut expected = BTreeMap::new();
+
+    for _ in 0..2000 {
+        let key = rand::random::<u32>() % 500;
+
+        if rand::random::<bool>() {
+            map.put(key, key * 2);
+            expected.insert(key, key * 2);
+        }
+        else {
+            map.delete(&key);
+            expected.remove(&key);
+        }
+
+        assert_eq!(map.len(), expected.len());
+    }
+
+    let actual: Vec<(u32, u32)> = map.iter().map(|(k, v)| (*k, *v)).collect();
+    let wanted: Vec<(u32, u32)> = expected.iter().map(|(k, v)| (*k, *v)).collect();
+    assert_eq!(actual, wanted);
+
+    for key in 0..500 {
+        assert_eq!(map.get(&key), expected.get(&key));
+        assert_eq!(map.floor(&key), expected.range(..=key).next_back().map(|(k, _)| k));
+        assert_eq!(map.ceiling(&key), expected.range(key..).next().map(|(k, _)| k));
+        assert_eq!(map.rank(&key), expected.range(..key).count());
+    }
+
+    for _ in 0..200 {
+        let lo = rand::random::<u32>() % 520;
+        let hi = lo + rand::random::<u32>() % 100;
+        let bounds = [Bound::Included(lo), Bound::Excluded(lo), Bound::Unbounded];
+
+        for start in bounds.iter() {
+            for end in [Bound::Included(hi), Bound::Excluded(hi), Bound::Unbounded].iter() {
+                if let (&Bound::Excluded(_), &Bound::Excluded(_)) = (start, end) {
+                    if lo == hi {
+                        // BTreeMap 不接受 (Excluded(x), Excluded(x))
+                        continue
+                    }
+                }
+
+                let actual: Vec<&u32> = map.range((*start, *end)).map(|(k, _)| k).collect();
+                let wanted: Vec<&u32> = expected.range((*start, *end)).map(|(k, _)| k).collect();
+                assert_eq!(actual, wanted);
+                assert_eq!(map.range_count((*start, *end)), wanted.len());
+            }
+        }
+    }
+}
+
+#[test]
+fn test() {
+    use super::binary_search_tree::BinarySearchTree;
+    use super::avl_tree::AvlTree;
+    use super::red_black_tree::RedBlackTree;
+
+    conformance(BinarySearchTree::new());
+    conformance(AvlTree::new());
+    conformance(RedBlackTree::new());
+
+    conformance_random(BinarySearchTree::new());
+    conformance_random(AvlTree::new());
+    conformance_random(RedBlackTree::new());
+}
@@ -1,5 +1,9 @@
 use std::mem;
 use std::cmp::Ordering;
+use std::iter::FromIterator;
+use std::ops::RangeBounds;
+use super::ordered_map::OrderedMap;
+use super::iter::{self, TreeNode};
 
 pub type Link<K, V> = Option<Box<Node<K, V>>>;
 
@@ -13,6 +17,27 @@ pub struct Node<K, V> {
     right: Link<K, V>,
 }
 
+pub type Iter<'a, K, V> = iter::Iter<'a, K, V, Node<K, V>>;
+pub type IterMut<'a, K, V> = iter::IterMut<'a, K, V, Node<K, V>>;
+pub type IntoIter<K, V> = iter::IntoIter<K, V, Node<K, V>>;
+pub type Keys<'a, K, V> = iter::Keys<'a, K, V, Node<K, V>>;
+pub type Values<'a, K, V> = iter::Values<'a, K, V, Node<K, V>>;
+pub type Range<'a, K, V, R> = iter::Range<'a, K, V, Node<K, V>, R>;
+
+impl<K, V> TreeNode<K, V> for Node<K, V> {
+    fn parts(&self) -> (&K, &V, Option<&Self>, Option<&Self>) {
+        (&self.key, &self.val, self.left.as_deref(), self.right.as_deref())
+    }
+
+    fn parts_mut(&mut self) -> (&K, &mut V, Option<&mut Self>, Option<&mut Self>) {
+        (&self.key, &mut self.val, self.left.as_deref_mut(), self.right.as_deref_mut())
+    }
+
+    fn into_parts(self) -> (K, V, Option<Box<Self>>, Option<Box<Self>>) {
+        (self.key, self.val, self.left, self.right)
+    }
+}
+
 #[derive(Debug)]
 enum Colors {
     RED,
@@ -27,8 +52,8 @@ enum FlipType {
 trait LinkMethods<K, V> {
     fn new(key: K, val: V) -> Link<K, V>;
     fn put(&mut self, key: K, val: V);
-    fn get(&self, key: K) -> Option<&V>;
-    fn delete(&mut self, key: K);
+    fn get(&self, key: &K) -> Option<&V>;
+    fn delete(&mut self, key: &K);
     fn delete_min(&mut self);
     fn delete_max(&mut self);
     fn size(&self) -> usize;
@@ -49,9 +74,9 @@ trait LinkMethods<K, V> {
     fn move_red_left(&mut self);
     fn move_red_right(&mut self);
     fn select(&self, k: usize) -> &Link<K, V>;
-    fn rank(&self, key: K) -> usize;
-    fn floor(&self, key: K) -> &Link<K, V>;
-    fn ceiling(&self, key: K) -> &Link<K, V>;
+    fn rank(&self, key: &K) -> usize;
+    fn floor(&self, key: &K) -> &Link<K, V>;
+    fn ceiling(&self, key: &K) -> &Link<K, V>;
     fn pre_order(&self) -> Vec<&Node<K, V>>;
     fn in_order(&self) -> Vec<&Node<K, V>>;
     fn post_order(&self) -> Vec<&Node<K, V>>;
@@ -85,8 +110,8 @@ impl<K: PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
         self.balance();
     }
 
-    fn get(&self, key: K) -> Option<&V> {
-        match Self::compare_key(&key, &self) {
+    fn get(&self, key: &K) -> Option<&V> {
+        match Self::compare_key(key, self) {
             Some(Ordering::Less) => self.left().get(key),
             Some(Ordering::Greater) => self.right().get(key),
             Some(Ordering::Equal) => Some(&self.as_ref().unwrap().val),
@@ -94,8 +119,8 @@ impl<K: PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
         }
     }
 
-    fn delete(&mut self, key: K) {
-        match Self::compare_key(&key, &self) {
+    fn delete(&mut self, key: &K) {
+        match Self::compare_key(key, self) {
             Some(Ordering::Less) => {
                 // 确保左侧节点为红色
                 if ! self.left().is_red() && ! self.left().left().is_red() {
@@ -110,7 +135,7 @@ impl<K: PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
                     self.rotate_right();
                 }
 
-                if let Some(Ordering::Equal) = Self::compare_key(&key, &self) {
+                if let Some(Ordering::Equal) = Self::compare_key(key, self) {
                     if self.right().is_none() {
                         *self = None;
                         return
@@ -123,7 +148,7 @@ impl<K: PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
                 }
 
                 // 经过旋转之后，当前节点匹配成功的话，右侧节点必定不为空
-                if let Some(Ordering::Equal) = Self::compare_key(&key, &self) {
+                if let Some(Ordering::Equal) = Self::compare_key(key, self) {
                     if let Some(mut boxed_node) = self.take() {
                         {
                             let node = &mut *boxed_node;
@@ -232,11 +257,16 @@ impl<K: PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
     }
 
     fn min_mut(&mut self) -> &mut Self {
-        match {self} {
-            &mut Some(ref mut node) if node.left.is_some() => {
-                node.left.min_mut()
-            },
-            node @ &mut Some(_) | node @ &mut None => node,
+        let has_left = match *self {
+            Some(ref node) => node.left.is_some(),
+            None => false,
+        };
+
+        if has_left {
+            self.as_mut().unwrap().left.min_mut()
+        }
+        else {
+            self
         }
     }
 
@@ -401,8 +431,8 @@ impl<K: PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
         }
     }
 
-    fn rank(&self, key: K) -> usize {
-        match Self::compare_key(&key, &self) {
+    fn rank(&self, key: &K) -> usize {
+        match Self::compare_key(key, self) {
             Some(Ordering::Less) => self.left().rank(key),
             Some(Ordering::Greater) => self.left().size() + self.right().rank(key) + 1,
             Some(Ordering::Equal) => self.left().size(),
@@ -410,8 +440,8 @@ impl<K: PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
         }
     }
 
-    fn floor(&self, key: K) -> &Self {
-        match Self::compare_key(&key, &self) {
+    fn floor(&self, key: &K) -> &Self {
+        match Self::compare_key(key, self) {
             Some(Ordering::Less) => self.left().floor(key),
             Some(Ordering::Greater) => {
                 let node = self.right().floor(key);
@@ -423,12 +453,13 @@ impl<K: PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
                     node
                 }
             },
-            Some(Ordering::Equal) | None => &None,
+            Some(Ordering::Equal) => self,
+            None => &None,
         }
     }
 
-    fn ceiling(&self, key: K) -> &Self {
-        match Self::compare_key(&key, &self) {
+    fn ceiling(&self, key: &K) -> &Self {
+        match Self::compare_key(key, self) {
             Some(Ordering::Less) => {
                 let node = self.left().ceiling(key);
 
@@ -439,10 +470,12 @@ impl<K: PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
                 }
             },
             Some(Ordering::Greater) => self.right().ceiling(key),
-            Some(Ordering::Equal) | None => &None,
+            Some(Ordering::Equal) => self,
+            None => &None,
         }
     }
 
+
     // 前序遍历
     fn pre_order(&self) -> Vec<&Node<K, V>> {
         let mut stack : Vec<&Node<K, V>> = Vec::new();
@@ -558,27 +591,36 @@ impl<K: PartialOrd, V> RedBlackTree<K, V> {
         RedBlackTree { root: None }
     }
 
-    pub fn put(&mut self, key: K, val: V) {
-        self.root.put(key, val);
+    // 按键的顺序遍历键值对，支持双端迭代
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(self.root.as_deref(), self.root.size())
     }
 
-    pub fn get(&self, key: K) -> Option<&V> {
-        self.root.get(key)
+    // 按键的顺序遍历键值对，可以修改值
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        let len = self.root.size();
+        IterMut::new(self.root.as_deref_mut(), len)
     }
 
-    pub fn delete(&mut self, key: K) {
-        if ! self.root.left().is_red() && ! self.root.right().is_red() {
-            self.root.as_mut().map(|node| node.color = Colors::RED);
-        }
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys::new(self.iter())
+    }
 
-        self.root.delete(key);
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values::new(self.iter())
+    }
 
-        if self.root.size() > 0 {
-            self.root.as_mut().map(|node| node.color = Colors::BLACK);
-        }
+    // 按键的顺序遍历范围内的键值对，如 tree.range(lo..hi)
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Range<'_, K, V, R> {
+        let len = self.range_count((range.start_bound(), range.end_bound()));
+        Range::new(self.root.as_deref(), range, len)
     }
 
     pub fn delete_min(&mut self) {
+        if self.root.is_none() {
+            return
+        }
+
         if ! self.root.left().is_red() && ! self.root.right().is_red() {
             self.root.as_mut().map(|node| node.color = Colors::RED);
         }
@@ -591,6 +633,10 @@ impl<K: PartialOrd, V> RedBlackTree<K, V> {
     }
 
     pub fn delete_max(&mut self) {
+        if self.root.is_none() {
+            return
+        }
+
         if ! self.root.left().is_red() && ! self.root.right().is_red() {
             self.root.as_mut().map(|node| node.color = Colors::RED);
         }
@@ -606,47 +652,168 @@ impl<K: PartialOrd, V> RedBlackTree<K, V> {
         self.root.size()
     }
 
-    pub fn min(&self) -> &Link<K, V> {
-        self.root.min()
+    pub fn pre_order(&self) -> Vec<&Node<K, V>> {
+        self.root.pre_order()
+    }
+
+    pub fn in_order(&self) -> Vec<&Node<K, V>> {
+        self.root.in_order()
+    }
+
+    pub fn post_order(&self) -> Vec<&Node<K, V>> {
+        self.root.post_order()
+    }
+
+    pub fn level_order(&self) -> Vec<&Node<K, V>> {
+        self.root.level_order()
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.root.get(key)
+    }
+
+    pub fn put(&mut self, key: K, val: V) {
+        self.root.put(key, val);
+
+        if let Some(ref mut node) = self.root {
+            node.color = Colors::BLACK;
+        }
+    }
+
+    pub fn delete(&mut self, key: &K) {
+        if ! self.contains(key) {
+            return
+        }
+
+        if ! self.root.left().is_red() && ! self.root.right().is_red() {
+            self.root.as_mut().map(|node| node.color = Colors::RED);
+        }
+
+        self.root.delete(key);
+
+        if self.root.size() > 0 {
+            self.root.as_mut().map(|node| node.color = Colors::BLACK);
+        }
+    }
+
+    pub fn min(&self) -> Option<&K> {
+        self.root.min().as_ref().map(|node| &node.key)
     }
 
-    pub fn max(&self) -> &Link<K, V> {
-        self.root.max()
+    pub fn max(&self) -> Option<&K> {
+        self.root.max().as_ref().map(|node| &node.key)
     }
 
-    pub fn select(&self, k: usize) -> &Link<K, V> {
-        self.root.select(k)
+    pub fn floor(&self, key: &K) -> Option<&K> {
+        self.root.floor(key).as_ref().map(|node| &node.key)
     }
 
-    pub fn rank(&self, key: K) -> usize {
+    pub fn ceiling(&self, key: &K) -> Option<&K> {
+        self.root.ceiling(key).as_ref().map(|node| &node.key)
+    }
+
+    pub fn rank(&self, key: &K) -> usize {
         self.root.rank(key)
     }
 
-    pub fn floor(&self, key: K) -> &Link<K, V> {
-        self.root.floor(key)
+    pub fn select(&self, k: usize) -> Option<&K> {
+        self.root.select(k).as_ref().map(|node| &node.key)
     }
+}
 
-    pub fn ceiling(&self, key: K) -> &Link<K, V> {
-        self.root.ceiling(key)
+impl<K: PartialOrd, V> OrderedMap<K, V> for RedBlackTree<K, V> {
+    fn get(&self, key: &K) -> Option<&V> {
+        Self::get(self, key)
     }
 
-    pub fn pre_order(&self) -> Vec<&Node<K, V>> {
-        self.root.pre_order()
+    fn put(&mut self, key: K, val: V) {
+        Self::put(self, key, val);
     }
 
-    pub fn in_order(&self) -> Vec<&Node<K, V>> {
-        self.root.in_order()
+    fn delete(&mut self, key: &K) {
+        Self::delete(self, key);
     }
 
-    pub fn post_order(&self) -> Vec<&Node<K, V>> {
-        self.root.post_order()
+    fn min(&self) -> Option<&K> {
+        Self::min(self)
     }
 
-    pub fn level_order(&self) -> Vec<&Node<K, V>> {
-        self.root.level_order()
+    fn max(&self) -> Option<&K> {
+        Self::max(self)
+    }
+
+    fn floor(&self, key: &K) -> Option<&K> {
+        Self::floor(self, key)
+    }
+
+    fn ceiling(&self, key: &K) -> Option<&K> {
+        Self::ceiling(self, key)
+    }
+
+    fn rank(&self, key: &K) -> usize {
+        Self::rank(self, key)
+    }
+
+    fn select(&self, k: usize) -> Option<&K> {
+        Self::select(self, k)
+    }
+
+    fn range<'a, R: RangeBounds<K> + 'a>(&'a self, range: R) -> Box<dyn Iterator<Item = (&'a K, &'a V)> + 'a> {
+        Box::new(Self::range(self, range))
+    }
+
+    fn len(&self) -> usize {
+        self.root.size()
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = (&'a K, &'a V)> + 'a> {
+        Box::new(Iter::new(self.root.as_deref(), self.root.size()))
+    }
+}
+
+impl<K: PartialOrd, V> IntoIterator for RedBlackTree<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        let len = self.root.size();
+        IntoIter::new(self.root, len)
+    }
+}
+
+impl<'a, K: PartialOrd, V> IntoIterator for &'a RedBlackTree<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
     }
 }
 
+impl<'a, K: PartialOrd, V> IntoIterator for &'a mut RedBlackTree<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+impl<K: PartialOrd, V> FromIterator<(K, V)> for RedBlackTree<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tree = RedBlackTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<K: PartialOrd, V> Extend<(K, V)> for RedBlackTree<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, val) in iter {
+            self.put(key, val);
+        }
+    }
+}
 
 #[test]
 fn test() {
@@ -662,60 +829,60 @@ fn test() {
     tree.put("M", 8);
 
     // 不存在树中的key, 获取前继元素和后继元素
-    assert_eq!(tree.floor("J").as_ref().unwrap().key, "H");
-    assert_eq!(tree.ceiling("J").as_ref().unwrap().key, "M");
+    assert_eq!(tree.floor(&"J"), Some(&"H"));
+    assert_eq!(tree.ceiling(&"J"), Some(&"M"));
 
-    // 存在树中的key, 获取前继元素和后继元素
-    assert_eq!(tree.floor("R").as_ref().unwrap().key, "M");
-    assert_eq!(tree.ceiling("R").as_ref().unwrap().key, "S");
+    // 存在树中的key, 就是它本身
+    assert_eq!(tree.floor(&"R"), Some(&"R"));
+    assert_eq!(tree.ceiling(&"R"), Some(&"R"));
 
     // 最小值和最大值
-    assert_eq!(tree.min().as_ref().unwrap().key, "A");
-    assert_eq!(tree.max().as_ref().unwrap().key, "X");
+    assert_eq!(tree.min(), Some(&"A"));
+    assert_eq!(tree.max(), Some(&"X"));
 
     // 选择第k个元素
-    assert_eq!(tree.select(0).as_ref().unwrap().key, "A");
-    assert_eq!(tree.select(1).as_ref().unwrap().key, "C");
-    assert_eq!(tree.select(2).as_ref().unwrap().key, "E");
-    assert_eq!(tree.select(3).as_ref().unwrap().key, "H");
-    assert_eq!(tree.select(4).as_ref().unwrap().key, "M");
-    assert_eq!(tree.select(5).as_ref().unwrap().key, "R");
-    assert_eq!(tree.select(6).as_ref().unwrap().key, "S");
-    assert_eq!(tree.select(7).as_ref().unwrap().key, "X");
-    assert!(tree.select(8).is_none());
+    assert_eq!(tree.select(0), Some(&"A"));
+    assert_eq!(tree.select(1), Some(&"C"));
+    assert_eq!(tree.select(2), Some(&"E"));
+    assert_eq!(tree.select(3), Some(&"H"));
+    assert_eq!(tree.select(4), Some(&"M"));
+    assert_eq!(tree.select(5), Some(&"R"));
+    assert_eq!(tree.select(6), Some(&"S"));
+    assert_eq!(tree.select(7), Some(&"X"));
+    assert_eq!(tree.select(8), None);
 
     // 查看元素的排名
-    assert_eq!(tree.rank("A"), 0);
-    assert_eq!(tree.rank("C"), 1);
-    assert_eq!(tree.rank("E"), 2);
-    assert_eq!(tree.rank("H"), 3);
-    assert_eq!(tree.rank("M"), 4);
-    assert_eq!(tree.rank("R"), 5);
-    assert_eq!(tree.rank("S"), 6);
-    assert_eq!(tree.rank("X"), 7);
+    assert_eq!(tree.rank(&"A"), 0);
+    assert_eq!(tree.rank(&"C"), 1);
+    assert_eq!(tree.rank(&"E"), 2);
+    assert_eq!(tree.rank(&"H"), 3);
+    assert_eq!(tree.rank(&"M"), 4);
+    assert_eq!(tree.rank(&"R"), 5);
+    assert_eq!(tree.rank(&"S"), 6);
+    assert_eq!(tree.rank(&"X"), 7);
 
     // 查看元素个数
     assert_eq!(tree.size(), 8);
 
     // 获取值
-    assert_eq!(tree.get("S"), Some(&1));
+    assert_eq!(tree.get(&"S"), Some(&1));
 
     // 删除最小元素
     tree.delete_min();
     assert_eq!(tree.size(), 7);
-    assert!(tree.get("A").is_none());
-    assert_eq!(tree.select(0).as_ref().unwrap().key, "C");
+    assert!(tree.get(&"A").is_none());
+    assert_eq!(tree.select(0), Some(&"C"));
 
     // 删除最大元素
     tree.delete_max();
     assert_eq!(tree.size(), 6);
-    assert!(tree.get("X").is_none());
-    assert_eq!(tree.select(5).as_ref().unwrap().key, "S");
+    assert!(tree.get(&"X").is_none());
+    assert_eq!(tree.select(5), Some(&"S"));
 
     // 根据key删除元素
-    tree.delete("S");
+    tree.delete(&"S");
     assert_eq!(tree.size(), 5);
-    assert!(tree.get("S").is_none());
+    assert!(tree.get(&"S").is_none());
 
     tree.pre_order();
 }
\ No newline at end of file